@@ -0,0 +1,117 @@
+//! Derive macros of [rs_quickcheck_util](https://docs.rs/rs_quickcheck_util).
+//!
+//! They are re-exported by `rs_quickcheck_util` with its `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index};
+
+/// Derives a `shrink_fields` method, shrinking fields one at a time.
+///
+/// The method returns a boxed iterator, which chains
+/// [shrink_a_field](https://docs.rs/rs_quickcheck_util/latest/rs_quickcheck_util/macro.shrink_a_field.html)
+/// of every field in declaration order,
+/// just like `shrink_fields!` with all fields listed.
+/// So it can be the whole body of `shrink`.
+///
+/// Fields marked by `#[shrink(skip)]` are kept untouched.
+///
+/// ```rust
+/// use rs_quickcheck_util::ShrinkFields;
+/// use quickcheck::{Arbitrary, Gen};
+///
+/// #[derive(Debug, Clone, ShrinkFields)]
+/// struct T {
+///     a: i64,
+///     #[shrink(skip)]
+///     id: u64,
+/// }
+///
+/// impl Arbitrary for T {
+///     fn arbitrary(g: &mut Gen) -> Self {
+///         T {
+///             a: i64::arbitrary(g),
+///             id: u64::arbitrary(g),
+///         }
+///     }
+///
+///     fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+///         self.shrink_fields()
+///     }
+/// }
+///
+/// let t = T { a: 100, id: 42 };
+/// for x in t.shrink() {
+///     assert_eq!(x.id, 42);
+/// }
+/// ```
+#[proc_macro_derive(ShrinkFields, attributes(shrink))]
+pub fn derive_shrink_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(x) => x.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Struct(x) => x,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "ShrinkFields can only be derived for structs",
+            ));
+        }
+    };
+    let mut fields = vec![];
+    for (i, field) in data.fields.iter().enumerate() {
+        if is_skipped(field)? {
+            continue;
+        }
+        let member = match (&data.fields, &field.ident) {
+            (Fields::Named(_), Some(ident)) => quote!(#ident),
+            _ => {
+                let i = Index::from(i);
+                quote!(#i)
+            }
+        };
+        fields.push(member);
+    }
+    let body = if fields.is_empty() {
+        quote!(Box::new(std::iter::empty()))
+    } else {
+        quote!(::rs_quickcheck_util::shrink_fields!(self, #(#fields),*))
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Shrinks fields one at a time, except those marked by `#[shrink(skip)]`.
+            pub fn shrink_fields(&self) -> Box<dyn Iterator<Item = Self>>
+            where Self: Clone + 'static
+            {
+                #body
+            }
+        }
+    })
+}
+
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut res = false;
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident("shrink") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                res = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown shrink attribute, expecting `skip`"))
+            }
+        })?;
+    }
+    Ok(res)
+}
@@ -0,0 +1,125 @@
+use crate::random::gen_below;
+use quickcheck::Gen;
+
+type Ctor<T> = Box<dyn Fn(&mut Gen) -> T>;
+
+/// Generates values across all variants of an enum, exhaustively first.
+///
+/// When a property fails only on a rarely generated variant,
+/// random selection can take many runs to hit it.
+/// `AllVariants` is given a list of constructors, one per variant.
+/// The first `constructors.len()` calls of [next](Self::next) cycle through them in order,
+/// so every variant is covered early.
+/// After that, constructors are uniformly chosen.
+///
+/// ```rust
+/// use rs_quickcheck_util::AllVariants;
+/// use quickcheck::{Arbitrary, Gen};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Shape {
+///     Circle(u8),
+///     Rect(u8, u8),
+///     Empty,
+/// }
+///
+/// let mut av = AllVariants::new()
+///     .variant(|g| Shape::Circle(u8::arbitrary(g)))
+///     .variant(|g| Shape::Rect(u8::arbitrary(g), u8::arbitrary(g)))
+///     .variant(|_| Shape::Empty);
+/// let mut g = Gen::new(32);
+/// assert!(matches!(av.next(&mut g), Shape::Circle(_)));
+/// assert!(matches!(av.next(&mut g), Shape::Rect(_, _)));
+/// assert_eq!(av.next(&mut g), Shape::Empty);
+/// ```
+pub struct AllVariants<T> {
+    constructors: Vec<Ctor<T>>,
+    calls: usize,
+}
+
+impl<T> Default for AllVariants<T> {
+    fn default() -> Self {
+        Self {
+            constructors: vec![],
+            calls: 0,
+        }
+    }
+}
+
+impl<T> AllVariants<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a constructor of a variant.
+    pub fn variant<F>(mut self, f: F) -> Self
+    where F: Fn(&mut Gen) -> T + 'static
+    {
+        self.constructors.push(Box::new(f));
+        self
+    }
+
+    /// Generates a value.
+    ///
+    /// It panics if there is no constructor.
+    pub fn next(&mut self, g: &mut Gen) -> T {
+        let n = self.constructors.len();
+        assert!(n > 0, "AllVariants: no constructor");
+        let idx = if self.calls < n {
+            self.calls
+        } else {
+            gen_below(g, n as u64) as usize
+        };
+        self.calls += 1;
+        (self.constructors[idx])(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum E {
+        A(u8),
+        B,
+        C(bool),
+        Rare,
+    }
+
+    fn tag(x: E) -> usize {
+        match x {
+            E::A(_) => 0,
+            E::B => 1,
+            E::C(_) => 2,
+            E::Rare => 3,
+        }
+    }
+
+    fn all_variants() -> super::AllVariants<E> {
+        super::AllVariants::new()
+            .variant(|g| E::A(u8::arbitrary(g)))
+            .variant(|_| E::B)
+            .variant(|g| E::C(bool::arbitrary(g)))
+            .variant(|_| E::Rare)
+    }
+
+    #[test]
+    fn exhaustive_first() {
+        let mut g = quickcheck::Gen::new(32);
+        let mut av = all_variants();
+        let tags: Vec<_> = (0..4).map(|_| tag(av.next(&mut g))).collect();
+        assert_eq!(tags, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn random_afterwards() {
+        let mut g = quickcheck::Gen::new(32);
+        let mut av = all_variants();
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            seen[tag(av.next(&mut g))] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+}
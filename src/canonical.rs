@@ -0,0 +1,108 @@
+use quickcheck::{Arbitrary, Gen};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// An idempotent canonicalizing transform, used by [Canonical].
+pub trait Canonicalize<T> {
+    /// Transforms a value into its canonical form.
+    ///
+    /// It must be idempotent, i.e., `canonicalize(canonicalize(x)) == canonicalize(x)`.
+    fn canonicalize(x: T) -> T;
+}
+
+/// Values already at a fixed point of a lossy transform.
+///
+/// For codecs with known lossy behavior (e.g., `f64` to `f32` and back),
+/// equality holds only on values already canonical.
+///
+/// *   `arbitrary` generates a `T` and canonicalizes it by `F`.
+/// *   `shrink` re-canonicalizes each shrinked candidate of `T`.
+///     Candidates equal to the current value are skipped.
+///
+/// As closures cannot be named in types, the transform is given by a type
+/// implementing [Canonicalize].
+///
+/// ```rust
+/// use rs_quickcheck_util::{Canonical, Canonicalize};
+/// use quickcheck::{Arbitrary, Gen};
+///
+/// #[derive(Debug)]
+/// struct RoundToTens;
+///
+/// impl Canonicalize<u32> for RoundToTens {
+///     fn canonicalize(x: u32) -> u32 {
+///         x - x % 10
+///     }
+/// }
+///
+/// let mut g = Gen::new(32);
+/// let x = Canonical::<u32, RoundToTens>::arbitrary(&mut g);
+/// assert_eq!(x.0 % 10, 0);
+/// ```
+pub struct Canonical<T, F>(pub T, PhantomData<fn() -> F>);
+
+impl<T, F: Canonicalize<T>> Canonical<T, F> {
+    /// Canonicalizes a value.
+    pub fn new(x: T) -> Self {
+        Canonical(F::canonicalize(x), PhantomData)
+    }
+}
+
+impl<T: Clone, F> Clone for Canonical<T, F> {
+    fn clone(&self) -> Self {
+        Canonical(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: Debug, F> Debug for Canonical<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Canonical").field(&self.0).finish()
+    }
+}
+
+impl<T: PartialEq, F> PartialEq for Canonical<T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T, F> Arbitrary for Canonical<T, F>
+where
+    T: Arbitrary + PartialEq,
+    F: Canonicalize<T> + 'static,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(T::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let me = self.0.clone();
+        Box::new(self.0.shrink()
+            .map(Self::new)
+            .filter(move |x| x.0 != me))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Canonical, Canonicalize};
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    struct RoundToTens;
+
+    impl Canonicalize<u32> for RoundToTens {
+        fn canonicalize(x: u32) -> u32 {
+            x - x % 10
+        }
+    }
+
+    #[quickcheck]
+    fn fixed_point(trial: Canonical<u32, RoundToTens>) {
+        assert_eq!(RoundToTens::canonicalize(trial.0), trial.0);
+        for x in trial.shrink() {
+            assert_eq!(RoundToTens::canonicalize(x.0), x.0);
+            assert!(x.0 < trial.0);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/// Chains boxed shrink iterators into one, preserving their order.
+///
+/// It saves nested `Box::new(a.chain(b).chain(c))`,
+/// especially when shrinkers are assembled dynamically.
+///
+/// ```rust
+/// use rs_quickcheck_util::{chain_shrinkers, shrink_a_field};
+///
+/// #[derive(Debug, Clone)]
+/// struct T {
+///     a: u32,
+///     b: Vec<u8>,
+/// }
+/// let t = T { a: 10, b: vec![1, 2] };
+/// let mut shrinkers: Vec<Box<dyn Iterator<Item = T>>> = vec![
+///     Box::new(shrink_a_field!(t, a)),
+/// ];
+/// if t.b.len() > 1 {
+///     shrinkers.push(Box::new(shrink_a_field!(t, b)));
+/// }
+/// assert!(chain_shrinkers(shrinkers).count() > 0);
+/// ```
+pub fn chain_shrinkers<T: 'static>(
+    iters: Vec<Box<dyn Iterator<Item = T>>>,
+) -> Box<dyn Iterator<Item = T>> {
+    Box::new(iters.into_iter().flatten())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn in_order() {
+        let xs = super::chain_shrinkers(vec![
+            Box::new(vec![1, 2].into_iter()),
+            Box::new(std::iter::empty()),
+            Box::new(std::iter::once(3)),
+            Box::new(4..6),
+        ]);
+        assert_eq!(xs.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(super::chain_shrinkers::<u8>(vec![]).count(), 0);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::Entropy;
+
+/// Chooses an item with possibility proportional to its weight.
+///
+/// Items of zero weight are never chosen.
+/// It returns `None` if `items` is empty, or all weights are zero.
+/// The index is chosen by rejection sampling, so there is no modulo bias.
+///
+/// ```rust
+/// use rs_quickcheck_util::choose_weighted;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// // mostly valid messages, occasionally malformed ones
+/// let x = choose_weighted(&mut g, &[("valid", 9), ("malformed", 1)]);
+/// assert!(x.is_some());
+/// ```
+pub fn choose_weighted<'a, E, T>(g: &mut E, items: &'a [(T, u32)]) -> Option<&'a T>
+where E: Entropy + ?Sized
+{
+    let weights: Vec<u32> = items.iter().map(|(_, w)| *w).collect();
+    if weights.iter().all(|w| *w == 0) {
+        return None;
+    }
+    Some(&items[crate::__weighted_index(g, &weights)].0)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn frequencies() {
+        const N: usize = 10000;
+        const ITEMS: &[(usize, u32)] = &[(0, 6), (1, 0), (2, 3), (3, 1)];
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut hist = [0usize; 4];
+        for _ in 0..N {
+            hist[*super::choose_weighted(&mut g, ITEMS).unwrap()] += 1;
+        }
+        assert_eq!(hist[1], 0);
+        for (i, w) in ITEMS.iter() {
+            let freq = hist[*i] as f64 / N as f64;
+            let expected = f64::from(*w) / 10.0;
+            assert!((freq - expected).abs() < 0.02, "i={}, freq={}", i, freq);
+        }
+    }
+
+    #[test]
+    fn none() {
+        let mut g = quickcheck::Gen::new(32);
+        let empty: &[(u8, u32)] = &[];
+        assert_eq!(super::choose_weighted(&mut g, empty), None);
+        assert_eq!(super::choose_weighted(&mut g, &[(1u8, 0)]), None);
+    }
+}
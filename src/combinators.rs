@@ -0,0 +1,168 @@
+use quickcheck::Gen;
+
+/// Maps outputs of a generator.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, gen_map};
+///
+/// let gen_str = gen_map(
+///     |g| gen_bytes(g, b"abc.", b'.', ..),
+///     |xs| String::from_utf8(xs).unwrap(),
+/// );
+/// let mut g = quickcheck::Gen::new(32);
+/// let s: String = gen_str(&mut g);
+/// assert!(s.chars().all(|c| "abc".contains(c)));
+/// ```
+pub fn gen_map<A, B, G, F>(gen: G, f: F) -> impl Fn(&mut Gen) -> B
+where
+    G: Fn(&mut Gen) -> A,
+    F: Fn(A) -> B,
+{
+    move |g| f(gen(g))
+}
+
+/// Filters outputs of a generator.
+///
+/// The resulting generator calls `gen` until `pred` holds on its output.
+/// After `max_tries` unsuccessful calls, it gives up and returns `None`.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, gen_filter};
+///
+/// let gen_nonempty = gen_filter(
+///     |g| gen_bytes(g, b"abc.", b'.', ..),
+///     |xs| !xs.is_empty(),
+///     100,
+/// );
+/// let mut g = quickcheck::Gen::new(32);
+/// if let Some(xs) = gen_nonempty(&mut g) {
+///     assert!(!xs.is_empty());
+/// }
+/// ```
+pub fn gen_filter<A, G, P>(gen: G, pred: P, max_tries: usize) -> impl Fn(&mut Gen) -> Option<A>
+where
+    G: Fn(&mut Gen) -> A,
+    P: Fn(&A) -> bool,
+{
+    move |g| (0..max_tries).map(|_| gen(g)).find(|x| pred(x))
+}
+
+/// Runs a generator exactly `N` times, collecting outputs into an array.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, repeat_gen};
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let [key, value] = repeat_gen::<_, 2>(&mut g, |g| gen_bytes(g, b"abc.", b'.', ..));
+/// assert!(key.iter().chain(value.iter()).all(|x| b"abc".contains(x)));
+/// ```
+pub fn repeat_gen<T, const N: usize>(g: &mut Gen, f: impl Fn(&mut Gen) -> T) -> [T; N] {
+    let xs: Vec<T> = (0..N).map(|_| f(g)).collect();
+    match xs.try_into() {
+        Ok(res) => res,
+        Err(_) => unreachable!(),
+    }
+}
+
+/// Yields generated values of growing sizes, for scaling benchmarks.
+///
+/// It calls `f` with size hints 1, 2, 4, 8, and so on,
+/// so each yielded value is roughly twice as large as the previous one.
+/// It ends when the size hint would overflow `usize`.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, growing};
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let inputs: Vec<_> = growing(&mut g, |g, n| gen_bytes(g, b"abc.", b'.', n..=n))
+///     .take(4)
+///     .collect();
+/// assert_eq!(inputs.iter().map(|x| x.len()).collect::<Vec<_>>(), vec![1, 2, 4, 8]);
+/// ```
+pub fn growing<'g, T>(
+    g: &'g mut Gen,
+    f: impl Fn(&mut Gen, usize) -> T + 'g,
+) -> impl Iterator<Item = T> + 'g {
+    std::iter::successors(Some(1usize), |n| n.checked_mul(2))
+        .map(move |n| f(g, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn map_to_string(seed: u64) {
+        let gen_str = super::gen_map(
+            |g| crate::gen_bytes(g, b"abc.", b'.', ..5),
+            |xs| String::from_utf8(xs).unwrap(),
+        );
+        let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+        let s = gen_str(&mut g);
+        assert!(s.len() < 5);
+        assert!(s.chars().all(|c| "abc".contains(c)));
+    }
+
+    #[quickcheck]
+    fn filter_nonempty(seed: u64) {
+        let gen_nonempty = super::gen_filter(
+            |g| crate::gen_bytes(g, b"abc.", b'.', ..),
+            |xs| !xs.is_empty(),
+            100,
+        );
+        let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+        let xs = gen_nonempty(&mut g).unwrap();
+        assert!(!xs.is_empty());
+    }
+
+    #[test]
+    fn filter_gives_up() {
+        let gen_none = super::gen_filter(
+            |g| crate::gen_bytes(g, b"abc.", b'.', ..),
+            |xs| xs.contains(&b'z'),
+            10,
+        );
+        let mut g = quickcheck::Gen::new(32);
+        assert_eq!(gen_none(&mut g), None);
+    }
+
+    #[test]
+    fn map_and_filter() {
+        let gen_str = super::gen_filter(
+            super::gen_map(
+                |g| crate::gen_bytes(g, b"abc.", b'.', ..),
+                |xs| String::from_utf8(xs).unwrap(),
+            ),
+            |s| s.starts_with('a'),
+            1000,
+        );
+        let mut g = quickcheck::Gen::new(32);
+        assert!(gen_str(&mut g).unwrap().starts_with('a'));
+    }
+
+    #[test]
+    fn repeat_distinct() {
+        let counter = std::cell::Cell::new(0);
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::repeat_gen::<_, 3>(&mut g, |_| {
+            counter.set(counter.get() + 1);
+            counter.get()
+        });
+        assert_eq!(xs, [1, 2, 3]);
+    }
+
+    #[test]
+    fn repeat_zero() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs: [u8; 0] = super::repeat_gen(&mut g, |_| unreachable!());
+        assert!(xs.is_empty());
+    }
+
+    #[test]
+    fn growing_sizes() {
+        let mut g = quickcheck::Gen::new(32);
+        let sizes: Vec<_> = super::growing(&mut g, |_, n| n).collect();
+        assert_eq!(sizes.len(), usize::BITS as usize);
+        assert!(sizes.windows(2).all(|w| w[0] < w[1]), "{:?}", sizes);
+    }
+}
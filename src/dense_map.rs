@@ -0,0 +1,75 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+use std::collections::BTreeMap;
+
+/// Sorted maps whose keys are exactly `0..n`.
+///
+/// It is useful to test dense-array-backed maps.
+///
+/// *   `arbitrary` generates values for keys `0..n`,
+///     where `n` is exponentially distributed.
+/// *   `shrink` first removes the highest keys, and then shrinks values one by one.
+///     So keys are always contiguous from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseMap<V>(pub BTreeMap<usize, V>);
+
+impl<V: Arbitrary> Arbitrary for DenseMap<V> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n = gen_len_by(g, .., |g| gen_below(g, 4) == 0);
+        DenseMap((0..n).map(|i| (i, V::arbitrary(g))).collect())
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let n = self.0.len();
+        let mut lens = vec![];
+        let mut removed = n;
+        while removed > 0 {
+            lens.push(n - removed);
+            removed /= 2;
+        }
+        let me = self.0.clone();
+        let truncated = lens.into_iter().map(move |len| {
+            DenseMap(me.range(..len).map(|(k, v)| (*k, v.clone())).collect())
+        });
+        let me = self.0.clone();
+        let values = (0..n).flat_map(move |i| {
+            let me = me.clone();
+            me[&i].shrink().map(move |v| {
+                let mut res = me.clone();
+                res.insert(i, v);
+                DenseMap(res)
+            })
+        });
+        Box::new(truncated.chain(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn check(m: &super::DenseMap<u32>) {
+        assert!(m.0.keys().copied().eq(0..m.0.len()), "{:?}", m);
+    }
+
+    #[quickcheck]
+    fn contiguous(trial: super::DenseMap<u32>) {
+        check(&trial);
+        for x in trial.shrink() {
+            check(&x);
+            assert!(x.0.len() <= trial.0.len());
+        }
+    }
+
+    #[test]
+    fn shrink_order() {
+        let m = super::DenseMap::<u32>((0..4).map(|i| (i, 10)).collect());
+        let xs: Vec<_> = m.shrink().collect();
+        assert_eq!(xs[0].0.len(), 0);
+        assert_eq!(xs[1].0.len(), 2);
+        assert_eq!(xs[2].0.len(), 3);
+        assert!(xs[3..].iter().all(|x| x.0.len() == 4));
+    }
+}
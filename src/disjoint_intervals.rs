@@ -0,0 +1,64 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::Unshrinkable;
+use quickcheck::{Arbitrary, Gen};
+
+/// Sorted, non-overlapping half-open intervals `[start, end)`.
+///
+/// It is useful to test interval trees and range-merging code.
+///
+/// *   `arbitrary` generates intervals, each of a positive length,
+///     separated by random gaps.
+///     Gaps may be empty, so an interval may be adjacent to its successor.
+///     The number of intervals is exponentially distributed.
+/// *   `shrink` removes intervals.
+///     As the remaining ones are kept as they are, they are still sorted and disjoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisjointIntervals(pub Vec<(u64, u64)>);
+
+impl Arbitrary for DisjointIntervals {
+    fn arbitrary(g: &mut Gen) -> Self {
+        const MAX_STEP: u64 = 16;
+        let n = gen_len_by(g, .., |g| gen_below(g, 4) == 0);
+        let mut res = Vec::with_capacity(n);
+        let mut cur = 0;
+        for _ in 0..n {
+            let start = cur + gen_below(g, MAX_STEP);
+            let end = start + 1 + gen_below(g, MAX_STEP);
+            res.push((start, end));
+            cur = end;
+        }
+        DisjointIntervals(res)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let xs: Vec<_> = self.0.iter().map(|x| Unshrinkable::new(*x)).collect();
+        Box::new(xs.shrink().map(|xs| {
+            DisjointIntervals(xs.into_iter().map(|x| x.take()).collect())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn check(xs: &[(u64, u64)]) {
+        for (start, end) in xs.iter() {
+            assert!(start < end, "{:?}", xs);
+        }
+        for w in xs.windows(2) {
+            assert!(w[0].1 <= w[1].0, "{:?}", xs);
+        }
+    }
+
+    #[quickcheck]
+    fn disjoint(trial: super::DisjointIntervals) {
+        check(&trial.0);
+        for x in trial.shrink() {
+            check(&x.0);
+            assert!(x.0.len() < trial.0.len());
+        }
+    }
+}
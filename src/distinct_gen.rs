@@ -0,0 +1,106 @@
+use quickcheck::Gen;
+
+/// Generates sequences which differ substantially from their predecessors.
+///
+/// For regression suites, it is often desired that each generated input differs
+/// substantially from the previous one, so the coverage spreads.
+/// `DistinctGen` remembers the last output.
+/// On each [next](Self::next), it regenerates until the new output is at least
+/// `min_distance` away from the last one, or `max_retries` retries have been made.
+/// In the latter case, the farthest candidate is returned.
+///
+/// The distance between two sequences is the Hamming distance of their common
+/// positions plus the difference of their lengths.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, DistinctGen};
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let mut dg = DistinctGen::new(2, 100);
+/// let xs = dg.next(&mut g, |g| gen_bytes(g, b"abcd.", b'.', 4..=4));
+/// let ys = dg.next(&mut g, |g| gen_bytes(g, b"abcd.", b'.', 4..=4));
+/// assert!(DistinctGen::distance(&xs, &ys) >= 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DistinctGen {
+    min_distance: usize,
+    max_retries: usize,
+    last: Option<Vec<u8>>,
+}
+
+impl DistinctGen {
+    pub fn new(min_distance: usize, max_retries: usize) -> Self {
+        Self {
+            min_distance,
+            max_retries,
+            last: None,
+        }
+    }
+
+    /// The last output, if any.
+    pub fn last(&self) -> Option<&[u8]> {
+        self.last.as_deref()
+    }
+
+    /// Generates a sequence by `gen`, which is distinct from the last output.
+    pub fn next<F>(&mut self, g: &mut Gen, gen: F) -> Vec<u8>
+    where F: Fn(&mut Gen) -> Vec<u8>
+    {
+        let mut best = gen(g);
+        if let Some(last) = self.last.as_ref() {
+            let mut best_dist = Self::distance(last, &best);
+            for _ in 0..self.max_retries {
+                if best_dist >= self.min_distance {
+                    break;
+                }
+                let x = gen(g);
+                let dist = Self::distance(last, &x);
+                if dist > best_dist {
+                    best = x;
+                    best_dist = dist;
+                }
+            }
+        }
+        self.last = Some(best.clone());
+        best
+    }
+
+    /// The distance between two sequences.
+    pub fn distance(xs: &[u8], ys: &[u8]) -> usize {
+        let hamming = xs.iter().zip(ys.iter()).filter(|(x, y)| x != y).count();
+        let len_diff = if xs.len() > ys.len() {
+            xs.len() - ys.len()
+        } else {
+            ys.len() - xs.len()
+        };
+        hamming + len_diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DistinctGen;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn consecutive(seed: u64) {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+        let mut dg = DistinctGen::new(4, 1000);
+        let xss: Vec<_> = (0..10)
+            .map(|_| dg.next(&mut g, |g| crate::gen_bytes(g, b"0123456789abcdef.", b'.', 8..=8)))
+            .collect();
+        for w in xss.windows(2) {
+            assert!(DistinctGen::distance(&w[0], &w[1]) >= 4, "{:?}", xss);
+        }
+        assert_eq!(dg.last(), xss.last().map(|x| x.as_slice()));
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(DistinctGen::distance(b"", b""), 0);
+        assert_eq!(DistinctGen::distance(b"abc", b"abc"), 0);
+        assert_eq!(DistinctGen::distance(b"abc", b"axc"), 1);
+        assert_eq!(DistinctGen::distance(b"abc", b"a"), 2);
+        assert_eq!(DistinctGen::distance(b"xbc", b"a"), 3);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+
+/// Applies `k` random structural edits to a vector in place.
+///
+/// Each edit is one of the following, equally likely:
+///
+/// *   inserting an arbitrary element at a random position,
+/// *   deleting a random element,
+/// *   replacing a random element by an arbitrary one.
+///
+/// On an empty vector, every edit is an insertion.
+/// This generalizes mutation-based fuzzing to any element type.
+pub fn edit_n<T: Arbitrary + Clone>(g: &mut Gen, xs: &mut Vec<T>, k: usize) {
+    for _ in 0..k {
+        let len = xs.len() as u64;
+        let op = if len == 0 { 0 } else { gen_below(g, 3) };
+        match op {
+            0 => {
+                let i = gen_below(g, len + 1) as usize;
+                xs.insert(i, T::arbitrary(g));
+            }
+            1 => {
+                let i = gen_below(g, len) as usize;
+                xs.remove(i);
+            }
+            _ => {
+                let i = gen_below(g, len) as usize;
+                xs[i] = T::arbitrary(g);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn length(xs: Vec<u32>, k: u8) {
+        let k = usize::from(k % 16);
+        let mut g = quickcheck::Gen::new(32);
+        let mut ys = xs.clone();
+        super::edit_n(&mut g, &mut ys, k);
+        let diff = if xs.len() > ys.len() {xs.len() - ys.len()} else {ys.len() - xs.len()};
+        assert!(diff <= k, "{:?} -> {:?}", xs, ys);
+    }
+
+    #[test]
+    fn edits_occur() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs: Vec<u32> = (0..10).collect();
+        let mut changed = 0;
+        for _ in 0..100 {
+            let mut ys = xs.clone();
+            super::edit_n(&mut g, &mut ys, 3);
+            if ys != xs {
+                changed += 1;
+            }
+        }
+        assert!(changed > 50, "changed={}", changed);
+        let mut ys = xs.clone();
+        super::edit_n(&mut g, &mut ys, 0);
+        assert_eq!(ys, xs);
+    }
+}
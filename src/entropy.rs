@@ -0,0 +1,164 @@
+use quickcheck::Gen;
+
+/// Sources of randomness.
+///
+/// Generators generic over `Entropy`, e.g., [gen_bytes](crate::gen_bytes) and
+/// [shuffle](crate::shuffle), work inside quickcheck by [Gen],
+/// as well as outside, e.g., in benchmarks, by a standalone source like [SeededEntropy].
+///
+/// Only [next_u64](Self::next_u64) is required.
+/// Generators draw all their randomness through it,
+/// so two sources yielding the same `u64`s lead to the same outputs.
+pub trait Entropy {
+    /// Generates a uniformly distributed `u64`.
+    fn next_u64(&mut self) -> u64;
+
+    /// Fills `dest` with uniformly distributed bytes.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let xs = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&xs[..chunk.len()]);
+        }
+    }
+
+    /// Generates a uniformly distributed index in `0..len`.
+    ///
+    /// Rejection sampling is applied, so there is no modulo bias.
+    /// `len` must be positive.
+    fn choose_index(&mut self, len: usize) -> usize {
+        crate::random::gen_below(self, len as u64) as usize
+    }
+}
+
+/// All bytes, so that `Gen::choose` on it yields a uniformly random byte.
+///
+/// Arbitrary integers of quickcheck are biased towards "problem values",
+/// e.g., 0 and the maximum value, so they are unsuitable as a source of
+/// uniform randomness.
+static BYTES: [u8; 256] = {
+    let mut xs = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        xs[i] = i as u8;
+        i += 1;
+    }
+    xs
+};
+
+impl Entropy for Gen {
+    fn next_u64(&mut self) -> u64 {
+        let mut res = 0u64;
+        for _ in 0..8 {
+            res = (res << 8) | u64::from(*self.choose(&BYTES).unwrap());
+        }
+        res
+    }
+}
+
+/// A standalone seeded source of randomness, i.e., SplitMix64.
+///
+/// It is deterministic for a given seed,
+/// and independent of quickcheck.
+/// Unlike [SeededGen](crate::SeededGen), whose sequence depends on quickcheck's PRNG,
+/// its sequence depends on nothing but the seed,
+/// so it replays the same values on every platform.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, SeededEntropy};
+///
+/// let xs = gen_bytes(&mut SeededEntropy::new(42), b"abc.", b'.', ..);
+/// let ys = gen_bytes(&mut SeededEntropy::new(42), b"abc.", b'.', ..);
+/// assert_eq!(xs, ys);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SeededEntropy {
+    state: u64,
+}
+
+impl SeededEntropy {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl Entropy for SeededEntropy {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entropy, SeededEntropy};
+    use quickcheck_macros::*;
+    use std::collections::VecDeque;
+
+    /// Replays recorded `u64`s.
+    struct Replay(VecDeque<u64>);
+
+    impl Entropy for Replay {
+        fn next_u64(&mut self) -> u64 {
+            self.0.pop_front().unwrap()
+        }
+    }
+
+    /// A source which records what it yields.
+    struct Record<E> {
+        inner: E,
+        log: Vec<u64>,
+    }
+
+    impl<E: Entropy> Entropy for Record<E> {
+        fn next_u64(&mut self) -> u64 {
+            let x = self.inner.next_u64();
+            self.log.push(x);
+            x
+        }
+    }
+
+    #[quickcheck]
+    fn gen_and_standalone_agree(seed: u64) {
+        let mut g = Record {
+            inner: quickcheck::Gen::from_size_and_seed(32, seed),
+            log: vec![],
+        };
+        let xs = crate::gen_bytes(&mut g, b"abcd.", b'.', ..);
+        let mut ys: Vec<u32> = (0..10).collect();
+        crate::shuffle(&mut g, &mut ys);
+        let mut r = Replay(g.log.into_iter().collect());
+        assert_eq!(crate::gen_bytes(&mut r, b"abcd.", b'.', ..), xs);
+        let mut zs: Vec<u32> = (0..10).collect();
+        crate::shuffle(&mut r, &mut zs);
+        assert_eq!(zs, ys);
+        assert!(r.0.is_empty());
+    }
+
+    #[quickcheck]
+    fn seeded_is_deterministic(seed: u64) {
+        let mut e0 = SeededEntropy::new(seed);
+        let mut e1 = SeededEntropy::new(seed);
+        assert_eq!(
+            crate::gen_bytes(&mut e0, b"abcd.", b'.', ..),
+            crate::gen_bytes(&mut e1, b"abcd.", b'.', ..),
+        );
+        let mut xs = [0u8; 13];
+        let mut ys = [0u8; 13];
+        e0.fill_bytes(&mut xs);
+        e1.fill_bytes(&mut ys);
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn choose_index_in_range() {
+        let mut e = SeededEntropy::new(0x5eed);
+        let mut seen = [false; 5];
+        for _ in 0..100 {
+            seen[e.choose_index(5)] = true;
+        }
+        assert_eq!(seen, [true; 5]);
+    }
+}
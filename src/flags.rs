@@ -0,0 +1,84 @@
+use crate::random::{gen_below, gen_ratio, gen_u64};
+use quickcheck::{Arbitrary, Gen};
+
+/// Bit-packed flag sets, with valid bits given by `VALID_MASK`.
+///
+/// *   `arbitrary` mostly sets only bits in `VALID_MASK`, uniformly.
+///     With possibility 1/8, it sets some reserved bits, i.e., bits not in `VALID_MASK`,
+///     as well to test validation.
+/// *   `shrink` clears bits towards zero.
+///     It tries clearing all reserved bits first,
+///     and then clearing set bits one by one, from the highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flags<const VALID_MASK: u32>(pub u32);
+
+impl<const VALID_MASK: u32> Flags<VALID_MASK> {
+    /// Whether no reserved bit is set.
+    pub fn is_valid(&self) -> bool {
+        self.0 & !VALID_MASK == 0
+    }
+}
+
+impl<const VALID_MASK: u32> Arbitrary for Flags<VALID_MASK> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut res = gen_u64(g) as u32 & VALID_MASK;
+        let reserved = !VALID_MASK;
+        if reserved != 0 && gen_ratio(g, 1, 8) {
+            let mut bits = gen_u64(g) as u32 & reserved;
+            if bits == 0 {
+                // sets at least one reserved bit
+                let candidates: Vec<_> = (0..32).filter(|i| reserved & (1 << i) != 0).collect();
+                bits = 1 << candidates[gen_below(g, candidates.len() as u64) as usize];
+            }
+            res |= bits;
+        }
+        Flags(res)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let x = self.0;
+        let mut res = vec![];
+        if !self.is_valid() && x & VALID_MASK != 0 {
+            res.push(Flags(x & VALID_MASK));
+        }
+        for i in (0..32).rev() {
+            if x & (1 << i) != 0 {
+                res.push(Flags(x & !(1 << i)));
+            }
+        }
+        Box::new(res.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    type F = super::Flags<0x0000_00ff>;
+
+    #[test]
+    fn mostly_valid() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        const N: usize = 1000;
+        let valid = (0..N).filter(|_| F::arbitrary(&mut g).is_valid()).count();
+        assert!(valid > N * 3 / 4, "valid={}", valid);
+        assert!(valid < N, "valid={}", valid);
+    }
+
+    #[test]
+    fn all_valid() {
+        let mut g = quickcheck::Gen::new(32);
+        for _ in 0..100 {
+            assert!(super::Flags::<{ u32::MAX }>::arbitrary(&mut g).is_valid());
+        }
+    }
+
+    #[quickcheck]
+    fn shrink_clears_bits(trial: F) {
+        for x in trial.shrink() {
+            assert!(x.0.count_ones() < trial.0.count_ones(), "{:?} -> {:?}", trial, x);
+            assert_eq!(x.0 & !trial.0, 0, "{:?} -> {:?}", trial, x);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::random::{gen_ratio, gen_u64};
+use crate::Generated;
+use quickcheck::{Arbitrary, Gen};
+
+/// The length of the checksum trailer of frames.
+pub const FRAME_TRAILER_LEN: usize = 4;
+
+/// Generates a frame, i.e., a body followed by a checksum trailer.
+///
+/// The body is generated by `body_gen`,
+/// and the trailer is `checksum(body)` in little endian.
+/// It returns the frame and the body as the model.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, gen_framed, shrink_framed};
+///
+/// fn checksum(xs: &[u8]) -> u32 {
+///     xs.iter().fold(0u32, |acc, x| acc.rotate_left(5) ^ u32::from(*x))
+/// }
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let frame = gen_framed(&mut g, |g| gen_bytes(g, b"abc.", b'.', ..), checksum);
+/// assert!(frame.bytes.starts_with(&frame.model));
+/// let frame = frame.bytes;
+/// for x in shrink_framed(&frame, checksum) {
+///     let (body, trailer) = x.split_at(x.len() - 4);
+///     assert_eq!(trailer, checksum(body).to_le_bytes());
+/// }
+/// ```
+pub fn gen_framed<F, C>(g: &mut Gen, body_gen: F, checksum: C) -> Generated<Vec<u8>>
+where
+    F: Fn(&mut Gen) -> Vec<u8>,
+    C: Fn(&[u8]) -> u32,
+{
+    let body = body_gen(g);
+    Generated {
+        bytes: frame(body.clone(), &checksum),
+        model: body,
+    }
+}
+
+/// Shrinks a frame, keeping every candidate a valid frame.
+///
+/// Shrinking the body naively breaks the checksum.
+/// This function shrinks the body, and recomputes the trailer for each shrinked body,
+/// so minimized counterexamples are still parseable.
+/// Frames shorter than the trailer cannot be shrinked.
+pub fn shrink_framed<C>(frame: &[u8], checksum: C) -> Box<dyn Iterator<Item = Vec<u8>>>
+where C: Fn(&[u8]) -> u32 + 'static
+{
+    if frame.len() < FRAME_TRAILER_LEN {
+        return quickcheck::empty_shrinker();
+    }
+    let body = frame[..frame.len() - FRAME_TRAILER_LEN].to_vec();
+    Box::new(body.shrink().map(move |body| self::frame(body, &checksum)))
+}
+
+/// Generates a frame whose checksum trailer is deliberately wrong at some rate.
+///
+/// To test both happy and error paths of checksum validation in one property,
+/// with possibility `bad_rate_num / bad_rate_den`,
+/// the trailer differs from `checksum(body)`.
+/// Otherwise, it is the same as [gen_framed].
+///
+/// It returns the frame and whether its checksum is valid.
+pub fn gen_checksummed<F, C>(
+    g: &mut Gen,
+    body_gen: F,
+    checksum: C,
+    bad_rate_num: u32,
+    bad_rate_den: u32,
+) -> (Vec<u8>, bool)
+where
+    F: Fn(&mut Gen) -> Vec<u8>,
+    C: Fn(&[u8]) -> u32,
+{
+    let body = body_gen(g);
+    if !gen_ratio(g, bad_rate_num, bad_rate_den) {
+        return (frame(body, &checksum), true);
+    }
+    let diff = loop {
+        let x = gen_u64(g) as u32;
+        if x != 0 {
+            break x;
+        }
+    };
+    let bad = checksum(&body) ^ diff;
+    (frame(body, &|_: &[u8]| bad), false)
+}
+
+fn frame<C>(mut body: Vec<u8>, checksum: &C) -> Vec<u8>
+where C: Fn(&[u8]) -> u32
+{
+    let trailer = checksum(&body).to_le_bytes();
+    body.extend(trailer);
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    fn checksum(xs: &[u8]) -> u32 {
+        xs.iter().fold(0x811c9dc5u32, |acc, x| (acc ^ u32::from(*x)).wrapping_mul(0x01000193))
+    }
+
+    fn check(frame: &[u8]) {
+        assert!(frame.len() >= super::FRAME_TRAILER_LEN);
+        let (body, trailer) = frame.split_at(frame.len() - super::FRAME_TRAILER_LEN);
+        assert_eq!(trailer, checksum(body).to_le_bytes(), "{:?}", frame);
+    }
+
+    #[quickcheck]
+    fn valid_frames(seed: u64) {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+        let crate::Generated { bytes: frame, model: body } =
+            super::gen_framed(&mut g, |g| crate::gen_bytes(g, b"abc.", b'.', ..), checksum);
+        check(&frame);
+        assert_eq!(frame[..frame.len() - super::FRAME_TRAILER_LEN], body[..]);
+        for x in super::shrink_framed(&frame, checksum) {
+            assert!(x.len() <= frame.len());
+            check(&x);
+        }
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(super::shrink_framed(b"abc", checksum).count(), 0);
+    }
+
+    #[test]
+    fn bad_rate() {
+        const N: usize = 10000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut bads = 0;
+        for _ in 0..N {
+            let (frame, valid) = super::gen_checksummed(
+                &mut g,
+                |g| crate::gen_bytes(g, b"abc.", b'.', ..),
+                checksum,
+                1,
+                4,
+            );
+            let (body, trailer) = frame.split_at(frame.len() - super::FRAME_TRAILER_LEN);
+            assert_eq!(valid, trailer == checksum(body).to_le_bytes());
+            if !valid {
+                bads += 1;
+            }
+        }
+        let rate = bads as f64 / N as f64;
+        assert!((rate - 0.25).abs() < 0.02, "rate={}", rate);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::gen_bytes::{gen_len_by, len_bounds};
+use crate::random::gen_below;
+use crate::Entropy;
+use std::ops::Bound;
+
+const HEAD: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_";
+const TAIL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_";
+
+/// Generates an ASCII identifier, i.e., `[A-Za-z_][A-Za-z0-9_]*`.
+///
+/// The length is exponentially distributed with stop possibility 1/16,
+/// except that the lower bound of `len_range` is raised to 1 if it is smaller,
+/// as an identifier is never empty.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+/// It panics if `len_range` allows no positive length.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_ascii_identifier;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let s = gen_ascii_identifier(&mut g, ..);
+/// assert!(!s.starts_with(|c: char| c.is_ascii_digit()));
+/// ```
+pub fn gen_ascii_identifier<E, R>(g: &mut E, len_range: R) -> String
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    let min_len = min_len.max(1);
+    assert!(
+        max_len.map_or(true, |n| n > min_len),
+        "gen_ascii_identifier: {:?} allows no positive length",
+        (len_range.start_bound(), len_range.end_bound()),
+    );
+    let max_len = max_len.map_or(Bound::Unbounded, Bound::Excluded);
+    let len = gen_len_by(g, (Bound::Included(min_len), max_len), |g| gen_below(g, 16) == 0);
+    let mut res = String::with_capacity(len);
+    res.push(char::from(HEAD[g.choose_index(HEAD.len())]));
+    for _ in 1..len {
+        res.push(char::from(TAIL[g.choose_index(TAIL.len())]));
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    fn is_identifier(s: &str) -> bool {
+        let mut cs = s.chars();
+        match cs.next() {
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+            _ => return false,
+        }
+        cs.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    #[quickcheck]
+    fn identifier_in_range(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let b = b.max(1);
+        let mut g = quickcheck::Gen::new(32);
+        let s = super::gen_ascii_identifier(&mut g, a..=b);
+        assert!(is_identifier(&s), "{:?}", s);
+        let n = s.chars().count();
+        assert!(n >= a.max(1), "left={}, right={}", n, a);
+        assert!(n <= b, "left={}, right={}", n, b);
+    }
+
+    #[test]
+    #[should_panic(expected = "allows no positive length")]
+    fn no_positive_length() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_ascii_identifier(&mut g, ..=0);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::random::gen_below;
+use crate::Generated;
+use quickcheck::Gen;
+use std::ops::Range;
+
+/// Generates a payload with a deliberately incorrect length prefix.
+///
+/// To test validation of length prefixes, e.g., a prefix claiming more or fewer
+/// bytes than present, this function generates a payload by `payload_gen`,
+/// and prepends a little-endian `u32` declared length,
+/// which is the true length skewed by a random amount in `skew_range`.
+///
+/// *   A skew of 0 is never applied, so `skew_range` must contain a non-zero value.
+/// *   The declared length is clamped into `u32`.
+///     If clamping makes it correct, the skew is applied in the opposite direction.
+///
+/// It returns the bytes and the (wrong) declared length as the model.
+pub fn gen_bad_length_prefixed<F>(
+    g: &mut Gen,
+    payload_gen: F,
+    skew_range: Range<i64>,
+) -> Generated<u32>
+where F: Fn(&mut Gen) -> Vec<u8>
+{
+    assert!(
+        skew_range.start < skew_range.end && skew_range != (0..1),
+        "gen_bad_length_prefixed: skew_range={:?} contains no non-zero value",
+        skew_range,
+    );
+    let payload = payload_gen(g);
+    let actual = payload.len() as i64;
+    assert!(actual <= i64::from(u32::MAX), "gen_bad_length_prefixed: payload is too long");
+    let width = skew_range.end.wrapping_sub(skew_range.start) as u64;
+    let skew = loop {
+        let x = skew_range.start.wrapping_add(gen_below(g, width) as i64);
+        if x != 0 {
+            break x;
+        }
+    };
+    let clamp = |x: i64| x.max(0).min(i64::from(u32::MAX));
+    let mut declared = clamp(actual.saturating_add(skew));
+    if declared == actual {
+        declared = clamp(actual.saturating_sub(skew));
+    }
+    let declared = declared as u32;
+    let mut res = Vec::with_capacity(4 + payload.len());
+    res.extend(declared.to_le_bytes());
+    res.extend(payload);
+    Generated {
+        bytes: res,
+        model: declared,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn wrong_length(a: i8, b: i8) {
+        let (a, b) = if a < b {
+            (i64::from(a), i64::from(b))
+        } else {
+            (i64::from(b), i64::from(a) + 1)
+        };
+        if (a..b) == (0..1) {
+            return;
+        }
+        let mut g = quickcheck::Gen::new(32);
+        let crate::Generated { bytes, model: declared } = super::gen_bad_length_prefixed(
+            &mut g,
+            |g| crate::gen_bytes(g, b"abc.", b'.', ..),
+            a..b,
+        );
+        let prefix = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        assert_eq!(prefix, declared);
+        let actual = (bytes.len() - 4) as i64;
+        assert_ne!(i64::from(declared), actual);
+        let skew = i64::from(declared) - actual;
+        // either skewed as is, clamped to 0, or skewed in the opposite direction
+        assert!(
+            (a..b).contains(&skew) || declared == 0 || (a..b).contains(&-skew),
+            "skew={}",
+            skew,
+        );
+    }
+}
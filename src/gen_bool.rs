@@ -0,0 +1,51 @@
+use crate::random::gen_unit_f64;
+use crate::Entropy;
+
+/// Returns `true` with possibility `p`, which must be in $[0, 1]$.
+///
+/// 53 random bits, i.e., the precision of `f64`, are drawn
+/// as a number uniformly distributed in $[0, 1)$ and compared with `p`.
+/// So there is no modulo bias.
+/// `p` of 0 never yields `true`, and `p` of 1 always does.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bool;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// // mostly on the happy path
+/// let error = gen_bool(&mut g, 0.1);
+/// ```
+pub fn gen_bool<E>(g: &mut E, p: f64) -> bool
+where E: Entropy + ?Sized
+{
+    assert!((0.0..=1.0).contains(&p), "gen_bool: p={} is not in [0, 1]", p);
+    gen_unit_f64(g) < p
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn frequencies() {
+        const N: usize = 10000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        for p in [0.01, 0.1, 0.5, 0.9] {
+            let n = (0..N).filter(|_| super::gen_bool(&mut g, p)).count();
+            let freq = n as f64 / N as f64;
+            assert!((freq - p).abs() < 0.02, "p={}, freq={}", p, freq);
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        let mut g = quickcheck::Gen::new(32);
+        assert!((0..1000).all(|_| !super::gen_bool(&mut g, 0.0)));
+        assert!((0..1000).all(|_| super::gen_bool(&mut g, 1.0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in [0, 1]")]
+    fn invalid_p() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bool(&mut g, 1.5);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Generates a map with arbitrary keys and values.
+///
+/// The number of entries is sampled as the length of [gen_vec](crate::gen_vec),
+/// and then keys and values are generated by `K::arbitrary` and `V::arbitrary`.
+/// Duplicated keys collapse, the later value winning.
+/// So the map may be smaller than the sampled number, even below the lower bound of
+/// `len_range`, but never larger.
+///
+/// It is not named `gen_map`, which maps outputs of a generator.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_btree_map;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let m = gen_btree_map::<u32, String, _>(&mut g, ..10);
+/// assert!(m.len() < 10);
+/// ```
+pub fn gen_btree_map<K, V, R>(g: &mut Gen, len_range: R) -> BTreeMap<K, V>
+where
+    K: Arbitrary + Ord,
+    V: Arbitrary,
+    R: std::ops::RangeBounds<usize>,
+{
+    let len = gen_len_by(g, len_range, |g| gen_below(g, 16) == 0);
+    (0..len).map(|_| (K::arbitrary(g), V::arbitrary(g))).collect()
+}
+
+/// Generates a [HashMap] with arbitrary keys and values.
+///
+/// It is [gen_btree_map] for hashable keys.
+pub fn gen_hash_map<K, V, R>(g: &mut Gen, len_range: R) -> HashMap<K, V>
+where
+    K: Arbitrary + Eq + Hash,
+    V: Arbitrary,
+    R: std::ops::RangeBounds<usize>,
+{
+    let len = gen_len_by(g, len_range, |g| gen_below(g, 16) == 0);
+    (0..len).map(|_| (K::arbitrary(g), V::arbitrary(g))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn btree_map_upper_bound(b: u8) {
+        let b = usize::from(b);
+        let mut g = quickcheck::Gen::new(32);
+        let m = super::gen_btree_map::<u8, u32, _>(&mut g, ..=b);
+        assert!(m.len() <= b, "left={}, right={}", m.len(), b);
+    }
+
+    #[quickcheck]
+    fn hash_map_upper_bound(b: u8) {
+        let b = usize::from(b);
+        let mut g = quickcheck::Gen::new(32);
+        let m = super::gen_hash_map::<u64, bool, _>(&mut g, ..b);
+        assert!(m.len() < b.max(1), "left={}, right={}", m.len(), b);
+    }
+
+    #[test]
+    fn collapsed_keys() {
+        let mut g = quickcheck::Gen::new(32);
+        let m = super::gen_btree_map::<bool, u32, _>(&mut g, 10..);
+        assert!(m.len() <= 2);
+        assert!(!m.is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::Entropy;
+
+/// Generates message lengths organized into bursts, for network-trace simulation.
+///
+/// Network and IO traces have bursty arrival patterns.
+/// The output is a sequence of bursts and gaps, alternately, starting with a burst.
+/// A burst is a number of back-to-back messages, each of a positive length.
+/// A gap is a number of zero-length placeholders.
+///
+/// *   `burst_size_range` is the range of the number of messages in a burst.
+/// *   `gap_range` is the range of the number of placeholders in a gap.
+/// *   `total_range` is the range of the total number of entries,
+///     i.e., messages and placeholders.
+///     The last burst or gap is cut short when the total is reached.
+///
+/// All of the numbers, as well as the message lengths, are exponentially distributed
+/// with stop possibility 1/16.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the ranges.
+pub fn gen_burst_lengths<E, R1, R2, R3>(
+    g: &mut E,
+    burst_size_range: R1,
+    gap_range: R2,
+    total_range: R3,
+) -> Vec<usize>
+where
+    E: Entropy + ?Sized,
+    R1: std::ops::RangeBounds<usize> + Clone,
+    R2: std::ops::RangeBounds<usize> + Clone,
+    R3: std::ops::RangeBounds<usize>,
+{
+    let total = gen_len_by(g, total_range, stop);
+    let mut res = Vec::with_capacity(total);
+    while res.len() < total {
+        let burst = gen_len_by(g, burst_size_range.clone(), stop);
+        for _ in 0..burst {
+            if res.len() >= total {
+                break;
+            }
+            res.push(gen_len_by(g, 1.., stop));
+        }
+        let gap = gen_len_by(g, gap_range.clone(), stop);
+        for _ in 0..gap {
+            if res.len() >= total {
+                break;
+            }
+            res.push(0);
+        }
+        if burst == 0 && gap == 0 {
+            // Neither bursts nor gaps take room. Fill up with gaps.
+            res.resize(total, 0);
+        }
+    }
+    res
+}
+
+fn stop<E: Entropy + ?Sized>(g: &mut E) -> bool {
+    gen_below(g, 16) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    /// Splits into runs of messages and placeholders.
+    fn runs(xs: &[usize]) -> Vec<(bool, usize)> {
+        let mut res: Vec<(bool, usize)> = vec![];
+        for x in xs.iter() {
+            let is_msg = *x > 0;
+            match res.last_mut() {
+                Some((m, n)) if *m == is_msg => *n += 1,
+                _ => res.push((is_msg, 1)),
+            }
+        }
+        res
+    }
+
+    #[quickcheck]
+    fn clustered(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_burst_lengths(&mut g, 4..=8, 2..=3, a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        let runs = runs(&xs);
+        for (i, (is_msg, n)) in runs.iter().enumerate() {
+            assert_eq!(*is_msg, i % 2 == 0, "{:?}", xs);
+            if i + 1 < runs.len() {
+                if *is_msg {
+                    assert!((4..=8).contains(n), "{:?}", xs);
+                } else {
+                    assert!((2..=3).contains(n), "{:?}", xs);
+                }
+            }
+        }
+    }
+}
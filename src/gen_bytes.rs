@@ -1,4 +1,4 @@
-use quickcheck::Gen;
+use crate::Entropy;
 
 /// Generate a sequence with exponentiall distributed length.
 /// It is often more efficient to find bugs by covering short inputs.
@@ -11,6 +11,7 @@ use quickcheck::Gen;
 ///
 ///     *   It is valid for the alphabet to contain duplicated characters.
 ///         For example, a alphabet `a..` with stoper `.` will make $p=2/3$.
+///     *   The alphabet must be non-empty. Otherwise, this function panics.
 ///
 /// *   the length range
 ///
@@ -39,43 +40,320 @@ use quickcheck::Gen;
 ///         This is invalid.
 ///         Then arbitrary sequence will be generated.
 ///
-pub fn gen_bytes<R>(
-    g: &mut Gen,
+///     *   unlimited upper bound without stopper
+///
+///         If the stopper is not in the alphabet, the sequence would never stop.
+///         So this function panics if the range has no upper bound in this case.
+///
+///     *   positive lower bound with only stoppers
+///
+///         If the alphabet consists of stoppers only, no character can be generated.
+///         So this function panics if the lower bound is positive in this case.
+///
+/// *   the source of randomness
+///
+///     It is generic over [Entropy],
+///     so it works with a quickcheck [Gen](quickcheck::Gen) as well as standalone sources.
+///
+pub fn gen_bytes<E, R>(
+    g: &mut E,
     alphabet: &[u8],
     stopper: u8,
     len_range: R,
 ) -> Vec<u8>
-where R: std::ops::RangeBounds<usize>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
 {
     let mut res = vec![];
+    fill_bytes("gen_bytes", g, alphabet, stopper, len_range, &mut res);
+    res
+}
+
+/// [gen_bytes] into a caller-provided buffer.
+///
+/// `out` is cleared, and then filled exactly as [gen_bytes] would generate.
+/// As it allocates nothing but growing `out`,
+/// allocations can be amortized by reusing the buffer across calls.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_into;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let mut buf = vec![];
+/// for _ in 0..100 {
+///     gen_bytes_into(&mut g, b"abc.", b'.', ..8, &mut buf);
+///     assert!(buf.len() < 8);
+/// }
+/// ```
+pub fn gen_bytes_into<E, R>(
+    g: &mut E,
+    alphabet: &[u8],
+    stopper: u8,
+    len_range: R,
+    out: &mut Vec<u8>,
+)
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    fill_bytes("gen_bytes_into", g, alphabet, stopper, len_range, out)
+}
+
+fn fill_bytes<E, R>(
+    name: &str,
+    g: &mut E,
+    alphabet: &[u8],
+    stopper: u8,
+    len_range: R,
+    out: &mut Vec<u8>,
+)
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    check_alphabet(name, alphabet, stopper, min_len, max_len);
+    let len = gen_len(g, stop_possibility(alphabet, stopper), len_range);
+    out.clear();
+    out.reserve(len);
+    for _ in 0..len {
+        out.push(choose_non_stopper(g, alphabet, stopper));
+    }
+}
+
+/// The stop possibility of an alphabet, without allocation unlike [split_alphabet].
+fn stop_possibility(alphabet: &[u8], stopper: u8) -> f64 {
+    let stoppers = alphabet.iter().filter(|x| **x == stopper).count();
+    stoppers as f64 / alphabet.len() as f64
+}
+
+/// Uniformly chooses a non-stopper character by rejection, without allocation.
+///
+/// `alphabet` must have a character but `stopper`.
+fn choose_non_stopper<E: Entropy + ?Sized>(g: &mut E, alphabet: &[u8], stopper: u8) -> u8 {
+    loop {
+        let x = alphabet[g.choose_index(alphabet.len())];
+        if x != stopper {
+            return x;
+        }
+    }
+}
+
+/// [gen_bytes] with a set of stoppers.
+///
+/// Any character in `stoppers` terminates the sequence.
+/// So the stop possibility is the number of characters of `alphabet` in `stoppers`
+/// over the size of `alphabet`.
+/// Please refer to [gen_bytes] for details.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_multi;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_bytes_multi(&mut g, b"abc;,", b";,\n", ..);
+/// assert!(xs.iter().all(|x| b"abc".contains(x)));
+/// ```
+pub fn gen_bytes_multi<E, R>(
+    g: &mut E,
+    alphabet: &[u8],
+    stoppers: &[u8],
+    len_range: R,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(!alphabet.is_empty(), "gen_bytes_multi: alphabet must be non-empty");
+    let (min_len, max_len) = len_bounds(&len_range);
+    let (p, chars) = split_alphabet(alphabet, stoppers);
+    assert!(
+        max_len.is_some() || p > 0.0,
+        "gen_bytes_multi: no stopper of {:?} is in alphabet {:?}, and the length is unbounded",
+        String::from_utf8_lossy(stoppers),
+        String::from_utf8_lossy(alphabet),
+    );
+    assert!(
+        min_len == 0 || !chars.is_empty(),
+        "gen_bytes_multi: alphabet {:?} has no character but stoppers {:?}, and the minimum length is {}",
+        String::from_utf8_lossy(alphabet),
+        String::from_utf8_lossy(stoppers),
+        min_len,
+    );
+    let len = gen_len(g, p, len_range);
+    (0..len).map(|_| chars[g.choose_index(chars.len())]).collect()
+}
+
+/// [gen_bytes] with an explicit stop possibility.
+///
+/// The alphabet needs no stopper character.
+/// Each byte is uniformly chosen from `alphabet`,
+/// and the length is sampled by [gen_len] with stop possibility `p`,
+/// which must be in $(0, 1]$.
+/// So the character distribution is decoupled from the length distribution.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_with_p;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_bytes_with_p(&mut g, b"ab", 0.05, ..);
+/// assert!(xs.iter().all(|x| b"ab".contains(x)));
+/// ```
+pub fn gen_bytes_with_p<E, R>(
+    g: &mut E,
+    alphabet: &[u8],
+    p: f64,
+    len_range: R,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(!alphabet.is_empty(), "gen_bytes_with_p: alphabet must be non-empty");
+    assert!(p > 0.0 && p <= 1.0, "gen_bytes_with_p: p={} is not in (0, 1]", p);
+    let len = gen_len(g, p, len_range);
+    (0..len).map(|_| alphabet[g.choose_index(alphabet.len())]).collect()
+}
+
+/// A lazy version of [gen_bytes].
+///
+/// It yields bytes on demand, following exactly the same distribution as [gen_bytes].
+/// To be precise, given the same source of randomness,
+/// it yields the same sequence as [gen_bytes] generates when fully consumed.
+/// So one can `take` a prefix, or feed a streaming parser, without allocation.
+///
+/// The length is decided upfront by [gen_len],
+/// and bytes are generated as they are requested.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_iter;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs: Vec<u8> = gen_bytes_iter(&mut g, b"abc.", b'.', 2..).take(2).collect();
+/// assert_eq!(xs.len(), 2);
+/// ```
+pub fn gen_bytes_iter<'g, E, R>(
+    g: &'g mut E,
+    alphabet: &'g [u8],
+    stopper: u8,
+    len_range: R,
+) -> impl Iterator<Item = u8> + 'g
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    check_alphabet("gen_bytes_iter", alphabet, stopper, min_len, max_len);
+    let len = gen_len(g, stop_possibility(alphabet, stopper), len_range);
+    (0..len).map(move |_| choose_non_stopper(g, alphabet, stopper))
+}
+
+/// Splits an alphabet into the stop possibility and non-stopper characters.
+pub(crate) fn split_alphabet(alphabet: &[u8], stoppers: &[u8]) -> (f64, Vec<u8>) {
+    let chars: Vec<u8> = alphabet.iter().copied().filter(|x| !stoppers.contains(x)).collect();
+    let stoppers = alphabet.len() - chars.len();
+    (stoppers as f64 / alphabet.len() as f64, chars)
+}
+
+/// Panics on an alphabet unable to generate sequences in the length range.
+pub(crate) fn check_alphabet(
+    name: &str,
+    alphabet: &[u8],
+    stopper: u8,
+    min_len: usize,
+    max_len: Option<usize>,
+) {
+    assert!(!alphabet.is_empty(), "{}: alphabet must be non-empty", name);
+    assert!(
+        max_len.is_some() || alphabet.contains(&stopper),
+        "{}: stopper {:?} is not in alphabet {:?}, and the length is unbounded",
+        name,
+        char::from(stopper),
+        String::from_utf8_lossy(alphabet),
+    );
+    assert!(
+        min_len == 0 || alphabet.iter().any(|x| *x != stopper),
+        "{}: alphabet {:?} has no character but stopper {:?}, and the minimum length is {}",
+        name,
+        String::from_utf8_lossy(alphabet),
+        char::from(stopper),
+        min_len,
+    );
+}
+
+/// Converts a length range into the minimum length and the exclusive maximum length.
+pub(crate) fn len_bounds<R>(len_range: &R) -> (usize, Option<usize>)
+where R: std::ops::RangeBounds<usize>
+{
     let min_len: usize = match len_range.start_bound() {
         std::ops::Bound::Unbounded => 0,
         std::ops::Bound::Included(n) => *n,
         std::ops::Bound::Excluded(n) => n + 1,
     };
-    while res.len() < min_len {
-        let ch = *g.choose(alphabet).unwrap();
-        if ch != stopper {
-            res.push(ch);
-        }
-    }
     let max_len: Option<usize> = match len_range.end_bound() {
         std::ops::Bound::Unbounded => None,
         std::ops::Bound::Included(n) => Some(n + 1),
         std::ops::Bound::Excluded(n) => Some(*n),
     };
+    (min_len, max_len)
+}
+
+/// Samples a length from the truncated geometric distribution of [gen_bytes].
+///
+/// `p` is the stop possibility, in $[0, 1]$.
+/// Please refer to [gen_bytes] for details on the distribution and the length range.
+/// It panics if `p` is 0 and the range has no upper bound,
+/// as the length would be infinite.
+///
+/// It is handy to decide how many elements to generate for custom collections.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_len;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let n = gen_len(&mut g, 0.25, 2..=5);
+/// assert!((2..=5).contains(&n));
+/// ```
+pub fn gen_len<E, R>(g: &mut E, p: f64, len_range: R) -> usize
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!((0.0..=1.0).contains(&p), "gen_len: p={} is not a possibility", p);
+    assert!(
+        p > 0.0 || len_bounds(&len_range).1.is_some(),
+        "gen_len: p is 0, and the length is unbounded",
+    );
+    gen_len_by(g, len_range, |g| crate::gen_bool(g, p))
+}
+
+/// Samples a length with the same distribution as [gen_bytes].
+///
+/// After the minimum length is reached,
+/// `stop` is called before each further step to decide whether to stop.
+pub(crate) fn gen_len_by<E, R, F>(
+    g: &mut E,
+    len_range: R,
+    mut stop: F,
+) -> usize
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+    F: FnMut(&mut E) -> bool,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    let mut res = min_len;
     loop {
-        let ch = *g.choose(alphabet).unwrap();
-        if ch == stopper {
+        if stop(g) {
             break;
         }
         match max_len {
-            Some(n) if res.len() + 1 >= n => {
+            Some(n) if res + 1 >= n => {
                 break;
             }
             _ => {}
         }
-        res.push(ch);
+        res += 1;
     }
     res
 }
@@ -117,4 +395,157 @@ mod tests {
         assert!(xs.iter().all(|x| ALPHABET.contains(x)));
         assert!(xs.iter().all(|x| *x != STOPPER));
     }
+
+    #[test]
+    #[should_panic(expected = "is not in alphabet")]
+    fn no_stopper_unbounded() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes(&mut g, b"abc", b'.', ..);
+    }
+
+    #[test]
+    fn no_stopper_bounded() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes(&mut g, b"abc", b'.', ..=5);
+        assert_eq!(xs.len(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no character but stopper")]
+    fn only_stoppers() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes(&mut g, b"..", b'.', 3..);
+    }
+
+    #[test]
+    fn only_stoppers_empty() {
+        let mut g = quickcheck::Gen::new(32);
+        assert!(super::gen_bytes(&mut g, b".", b'.', ..).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "alphabet must be non-empty")]
+    fn empty_alphabet() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes(&mut g, &[], b'.', ..=3);
+    }
+
+    #[quickcheck]
+    fn iter_equals_vec(seed: u64, a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g0 = quickcheck::Gen::from_size_and_seed(32, seed);
+        let mut g1 = quickcheck::Gen::from_size_and_seed(32, seed);
+        let xs = super::gen_bytes(&mut g0, b"abcd.", b'.', a..=b);
+        let ys: Vec<u8> = super::gen_bytes_iter(&mut g1, b"abcd.", b'.', a..=b).collect();
+        assert_eq!(xs, ys);
+        // both sources are left in the same state
+        assert_eq!(
+            super::gen_bytes(&mut g0, b"abcd.", b'.', ..),
+            super::gen_bytes(&mut g1, b"abcd.", b'.', ..),
+        );
+    }
+
+    #[test]
+    fn len_distribution() {
+        const N: usize = 100000;
+        const P: f64 = 0.25;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut hist = [0usize; 8];
+        for _ in 0..N {
+            let n = super::gen_len(&mut g, P, ..);
+            if n < hist.len() {
+                hist[n] += 1;
+            }
+        }
+        for (n, cnt) in hist.iter().enumerate() {
+            let expected = P * (1.0 - P).powi(n as i32);
+            let actual = *cnt as f64 / N as f64;
+            assert!((actual - expected).abs() < 0.01, "n={}, actual={}, expected={}", n, actual, expected);
+        }
+    }
+
+    #[quickcheck]
+    fn len_ranges(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let n = super::gen_len(&mut g, 0.1, a..=b);
+        assert!(n >= a, "left={}, right={}", n, a);
+        assert!(n <= b, "left={}, right={}", n, b);
+        let n = super::gen_len(&mut g, 0.1, a..);
+        assert!(n >= a, "left={}, right={}", n, a);
+        let n = super::gen_len(&mut g, 0.1, ..b + 1);
+        assert!(n <= b, "left={}, right={}", n, b);
+        assert_eq!(super::gen_len(&mut g, 1.0, a..), a);
+        assert_eq!(super::gen_len(&mut g, 0.0, ..=b), b);
+    }
+
+    #[quickcheck]
+    fn with_p(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_with_p(&mut g, b"ab", 0.05, a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| b"ab".contains(x)));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in (0, 1]")]
+    fn with_zero_p() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_with_p(&mut g, b"ab", 0.0, ..=3);
+    }
+
+    #[quickcheck]
+    fn multi_stoppers(a: u8, b: u8) {
+        const ALPHABET: &[u8] = b"abcd;,";
+        // partially overlapping with the alphabet
+        const STOPPERS: &[u8] = b";,\n";
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_multi(&mut g, ALPHABET, STOPPERS, a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| b"abcd".contains(x)), "{:?}", xs);
+    }
+
+    #[test]
+    fn multi_stop_possibility() {
+        const N: usize = 10000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let empties = (0..N)
+            .filter(|_| super::gen_bytes_multi(&mut g, b"ab;,", b";,\n", ..).is_empty())
+            .count();
+        // p = 2 / 4
+        let rate = empties as f64 / N as f64;
+        assert!((rate - 0.5).abs() < 0.02, "rate={}", rate);
+    }
+
+    #[quickcheck]
+    fn into_same_as_allocating(seed: u64) {
+        let mut g0 = quickcheck::Gen::from_size_and_seed(32, seed);
+        let mut g1 = quickcheck::Gen::from_size_and_seed(32, seed);
+        let mut buf = b"dirty".to_vec();
+        for _ in 0..8 {
+            let xs = super::gen_bytes(&mut g0, b"a.bc.", b'.', ..);
+            super::gen_bytes_into(&mut g1, b"a.bc.", b'.', .., &mut buf);
+            assert_eq!(xs, buf);
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use crate::gen_bytes::{gen_len_by, len_bounds};
+use crate::random::{gen_below, gen_ratio};
+use crate::Entropy;
+
+/// Generates a sequence of any bytes except the forbidden ones.
+///
+/// Bytes are uniformly chosen from `0..=255` except `forbidden`.
+/// It panics if every byte is forbidden and the sequence is non-empty.
+///
+/// The length is exponentially distributed with stop possibility
+/// `stop_num / stop_den`.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+/// It panics if `stop_num` is 0 and the length range is unbounded.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_avoiding;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_bytes_avoiding(&mut g, b"\0\n", 1, 16, ..);
+/// assert!(!xs.contains(&b'\0'));
+/// assert!(!xs.contains(&b'\n'));
+/// ```
+pub fn gen_bytes_avoiding<E, R>(
+    g: &mut E,
+    forbidden: &[u8],
+    stop_num: u32,
+    stop_den: u32,
+    len_range: R,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(
+        stop_num > 0 || len_bounds(&len_range).1.is_some(),
+        "gen_bytes_avoiding: stop_num is 0, and the length is unbounded",
+    );
+    let alphabet: Vec<u8> = (0..=u8::MAX).filter(|x| !forbidden.contains(x)).collect();
+    let len = gen_len_by(g, len_range, |g| gen_ratio(g, stop_num, stop_den));
+    assert!(
+        len == 0 || !alphabet.is_empty(),
+        "gen_bytes_avoiding: every byte is forbidden",
+    );
+    (0..len)
+        .map(|_| alphabet[gen_below(g, alphabet.len() as u64) as usize])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn avoiding(forbidden: Vec<u8>, a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_avoiding(&mut g, &forbidden, 1, 16, a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| !forbidden.contains(x)), "{:?}", xs);
+    }
+
+    #[test]
+    fn all_forbidden_but_one() {
+        let forbidden: Vec<u8> = (0..=u8::MAX).filter(|x| *x != b'a').collect();
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_avoiding(&mut g, &forbidden, 1, 16, 10..=10);
+        assert_eq!(xs, b"aaaaaaaaaa");
+    }
+
+    #[test]
+    #[should_panic(expected = "stop_num is 0, and the length is unbounded")]
+    fn never_stop() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_avoiding(&mut g, b"", 0, 8, ..);
+    }
+}
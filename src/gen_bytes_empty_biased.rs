@@ -0,0 +1,67 @@
+use crate::gen_bytes::len_bounds;
+use crate::random::gen_ratio;
+use crate::Entropy;
+
+/// [gen_bytes](crate::gen_bytes) with a higher possibility of the empty sequence.
+///
+/// To guarantee the empty-input path gets exercised, this function returns
+/// the empty sequence with possibility `empty_prob_num / empty_prob_den`,
+/// provided that `len_range` allows 0.
+/// Otherwise, it delegates to [gen_bytes](crate::gen_bytes),
+/// which may also generate the empty sequence by itself.
+pub fn gen_bytes_empty_biased<E, R>(
+    g: &mut E,
+    alphabet: &[u8],
+    stopper: u8,
+    len_range: R,
+    empty_prob_num: u32,
+    empty_prob_den: u32,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    let allows_empty = min_len == 0 && max_len.map_or(true, |n| n > 0);
+    if allows_empty && gen_ratio(g, empty_prob_num, empty_prob_den) {
+        return vec![];
+    }
+    crate::gen_bytes(g, alphabet, stopper, len_range)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    const STOPPER: u8 = b'.';
+    const ALPHABET: &[u8] = b"abcdefghijklmno.";
+
+    #[test]
+    fn rate() {
+        const N: usize = 10000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let empties = (0..N)
+            .filter(|_| super::gen_bytes_empty_biased(&mut g, ALPHABET, STOPPER, .., 1, 4).is_empty())
+            .count();
+        // 1/4 biased, plus 1/16 of the rest by gen_bytes itself
+        let expected = 0.25 + 0.75 / 16.0;
+        let rate = empties as f64 / N as f64;
+        assert!((rate - expected).abs() < 0.02, "rate={}, expected={}", rate, expected);
+    }
+
+    #[quickcheck]
+    fn range_respected(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_empty_biased(&mut g, ALPHABET, STOPPER, a..=b, 1, 1);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        if a == 0 {
+            assert!(xs.is_empty());
+        }
+    }
+}
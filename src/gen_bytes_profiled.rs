@@ -0,0 +1,96 @@
+use crate::gen_bytes::{gen_len_by, len_bounds};
+use crate::random::gen_ratio;
+use crate::Entropy;
+
+/// Generates a sequence honoring a byte-frequency profile.
+///
+/// To mimic a real corpus, one can pass observed byte frequencies.
+/// Byte `i` is emitted with possibility proportional to `freq[i]`.
+/// Frequencies cannot all be zero.
+///
+/// The length is exponentially distributed with stop possibility
+/// `stop_num / stop_den`.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+/// It panics if `stop_num` is 0 and the length range is unbounded.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_profiled;
+///
+/// let mut freq = [0u32; 256];
+/// freq[b'a' as usize] = 3;
+/// freq[b'b' as usize] = 1;
+/// let mut g = quickcheck::Gen::new(32);
+/// // mostly 'a's, some 'b's, and nothing else
+/// let xs = gen_bytes_profiled(&mut g, &freq, 1, 16, ..);
+/// assert!(xs.iter().all(|x| *x == b'a' || *x == b'b'));
+/// ```
+pub fn gen_bytes_profiled<E, R>(
+    g: &mut E,
+    freq: &[u32; 256],
+    stop_num: u32,
+    stop_den: u32,
+    len_range: R,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(
+        stop_num > 0 || len_bounds(&len_range).1.is_some(),
+        "gen_bytes_profiled: stop_num is 0, and the length is unbounded",
+    );
+    let len = gen_len_by(g, len_range, |g| gen_ratio(g, stop_num, stop_den));
+    (0..len)
+        .map(|_| crate::__weighted_index(g, freq) as u8)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    fn profile() -> [u32; 256] {
+        let mut freq = [0u32; 256];
+        freq[b'a' as usize] = 6;
+        freq[b'b' as usize] = 3;
+        freq[b'c' as usize] = 1;
+        freq
+    }
+
+    #[quickcheck]
+    fn length_and_alphabet(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_profiled(&mut g, &profile(), 1, 8, a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| b"abc".contains(x)));
+    }
+
+    #[test]
+    fn histogram() {
+        let freq = profile();
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let xs = super::gen_bytes_profiled(&mut g, &freq, 0, 1, 10000..=10000);
+        let mut hist = [0usize; 256];
+        for x in xs.iter() {
+            hist[*x as usize] += 1;
+        }
+        for (i, (h, f)) in hist.iter().zip(freq.iter()).enumerate() {
+            let expected = xs.len() * (*f as usize) / 10;
+            let diff = if *h > expected {h - expected} else {expected - h};
+            assert!(diff <= xs.len() / 50, "byte={}, count={}, expected={}", i, h, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "stop_num is 0, and the length is unbounded")]
+    fn never_stop() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_profiled(&mut g, &profile(), 0, 8, ..);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::gen_bytes::{gen_len_by, len_bounds};
+use crate::random::gen_ratio;
+use crate::Entropy;
+
+/// Generates a sequence byte by byte, each decided by the bytes generated so far.
+///
+/// `next` receives the prefix generated so far, and returns the byte to append.
+/// This gives full context-sensitive control, e.g., templated or markov generation,
+/// while the length is handled as usual.
+///
+/// The length is exponentially distributed with stop possibility
+/// `stop_num / stop_den`.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+/// It panics if `stop_num` is 0 and the length range is unbounded.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_stateful;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// // counts up from b'a'
+/// let xs = gen_bytes_stateful(&mut g, 1, 8, ..26, |_, prefix| b'a' + prefix.len() as u8);
+/// assert!(b"abcdefghijklmnopqrstuvwxyz".starts_with(&xs));
+/// ```
+pub fn gen_bytes_stateful<E, R, F>(
+    g: &mut E,
+    stop_num: u32,
+    stop_den: u32,
+    len_range: R,
+    next: F,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+    F: Fn(&mut E, &[u8]) -> u8,
+{
+    assert!(
+        stop_num > 0 || len_bounds(&len_range).1.is_some(),
+        "gen_bytes_stateful: stop_num is 0, and the length is unbounded",
+    );
+    let len = gen_len_by(g, len_range, |g| gen_ratio(g, stop_num, stop_den));
+    let mut res = Vec::with_capacity(len);
+    for _ in 0..len {
+        let x = next(g, &res);
+        res.push(x);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn no_equal_neighbors(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_stateful(&mut g, 1, 4, a..=b, |g, prefix| {
+            let candidates: Vec<u8> = b"abc".iter()
+                .copied()
+                .filter(|x| prefix.last() != Some(x))
+                .collect();
+            *g.choose(&candidates).unwrap()
+        });
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.windows(2).all(|w| w[0] != w[1]), "{:?}", xs);
+    }
+
+    #[test]
+    #[should_panic(expected = "stop_num is 0, and the length is unbounded")]
+    fn never_stop() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_stateful(&mut g, 0, 8, .., |_, _| 0);
+    }
+}
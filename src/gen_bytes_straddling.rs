@@ -0,0 +1,169 @@
+use crate::gen_bytes::{check_alphabet, len_bounds, split_alphabet};
+use crate::random::gen_below;
+use crate::{gen_len, Entropy};
+
+/// Generates a sequence with a token straddling a buffer-size boundary.
+///
+/// Streaming parsers have bugs when a token spans a read boundary.
+/// This function deliberately targets such off-by-one bugs.
+///
+/// The output consists of tokens, i.e., runs of non-stopper characters,
+/// delimited by `stopper`.
+/// Characters are chosen from `alphabet`, as [gen_bytes](crate::gen_bytes) does,
+/// so tokens are as long as sequences generated by [gen_bytes](crate::gen_bytes).
+///
+/// *   The distance between the length of the output and `boundary` is
+///     exponentially distributed in the same way.
+///     The output is equally likely to be shorter or longer than `boundary`.
+///     The length is clamped into `len_range`.
+/// *   Whenever the output extends beyond `boundary`, and `boundary` is positive,
+///     a token is placed across it,
+///     i.e., neither byte `boundary - 1` nor byte `boundary` is `stopper`.
+///
+/// It panics if `alphabet` is empty, if `stopper` is not in `alphabet`,
+/// if `len_range` is empty,
+/// or if the length is positive but `alphabet` has no character but `stopper`.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_straddling;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_bytes_straddling(&mut g, b"ab ", b' ', 8, ..);
+/// if xs.len() > 8 {
+///     assert!(xs[7] != b' ' && xs[8] != b' ');
+/// }
+/// ```
+pub fn gen_bytes_straddling<E, R>(
+    g: &mut E,
+    alphabet: &[u8],
+    stopper: u8,
+    boundary: usize,
+    len_range: R,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    const NAME: &str = "gen_bytes_straddling";
+    let (min_len, max_len) = len_bounds(&len_range);
+    assert!(
+        max_len.map_or(true, |n| n > min_len),
+        "{}: length range {:?} is empty",
+        NAME,
+        (len_range.start_bound(), len_range.end_bound()),
+    );
+    // the distance is unbounded, so the stopper is required
+    check_alphabet(NAME, alphabet, stopper, 0, None);
+    let (p, chars) = split_alphabet(alphabet, &[stopper]);
+    let dist = gen_len(g, p, ..);
+    let len = if gen_below(g, 2) == 0 {
+        boundary.saturating_add(dist)
+    } else {
+        boundary.saturating_sub(dist)
+    };
+    let len = match max_len {
+        Some(n) if len >= n => n - 1,
+        _ => len,
+    };
+    let len = len.max(min_len);
+    check_alphabet(NAME, alphabet, stopper, len, None);
+    let mut res: Vec<u8> = (0..len).map(|_| alphabet[g.choose_index(alphabet.len())]).collect();
+    if 0 < boundary && boundary < len {
+        // the token across `boundary`, delimited unless it reaches either end
+        let start = boundary - gen_len(g, p, 1..=boundary);
+        let end = boundary + gen_len(g, p, 1..=len - boundary);
+        for x in res[start..end].iter_mut() {
+            *x = chars[g.choose_index(chars.len())];
+        }
+        if start > 0 {
+            res[start - 1] = stopper;
+        }
+        if end < len {
+            res[end] = stopper;
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    const STOPPER: u8 = b'.';
+    const ALPHABET: &[u8] = b"a.";
+
+    #[test]
+    fn near_boundary() {
+        const BOUNDARY: usize = 4096;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let lens: Vec<_> = (0..1000)
+            .map(|_| super::gen_bytes_straddling(&mut g, ALPHABET, STOPPER, BOUNDARY, ..).len())
+            .collect();
+        assert!(lens.iter().all(|x| (BOUNDARY - 16..=BOUNDARY + 16).contains(x)), "{:?}", lens);
+        assert!(lens.iter().any(|x| *x < BOUNDARY));
+        assert!(lens.iter().any(|x| *x > BOUNDARY));
+        assert!(lens.contains(&BOUNDARY));
+    }
+
+    #[quickcheck]
+    fn clamped(boundary: u8, a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_straddling(&mut g, ALPHABET, STOPPER, boundary.into(), a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| ALPHABET.contains(x)));
+    }
+
+    #[test]
+    fn token_across_boundary() {
+        const BOUNDARY: usize = 64;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut crossing = 0;
+        let mut delimited = 0;
+        for _ in 0..1000 {
+            let xs = super::gen_bytes_straddling(&mut g, b"abc.", STOPPER, BOUNDARY, ..);
+            if xs.len() > BOUNDARY {
+                assert!(xs[BOUNDARY - 1] != STOPPER && xs[BOUNDARY] != STOPPER, "{:?}", xs);
+                crossing += 1;
+                if xs[..BOUNDARY].contains(&STOPPER) && xs[BOUNDARY..].contains(&STOPPER) {
+                    delimited += 1;
+                }
+            }
+        }
+        assert!(crossing > 300, "crossing={}", crossing);
+        assert!(delimited > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in alphabet")]
+    fn no_stopper() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_straddling(&mut g, b"ab", STOPPER, 16, ..);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no character but stopper")]
+    fn only_stoppers() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_straddling(&mut g, b"..", STOPPER, 16, ..);
+    }
+
+    #[test]
+    #[should_panic(expected = "alphabet must be non-empty")]
+    fn empty_alphabet() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_straddling(&mut g, b"", STOPPER, 16, ..);
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn empty_range() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_straddling(&mut g, ALPHABET, STOPPER, 16, 8..8);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::gen_bytes::len_bounds;
+use crate::random::gen_between;
+use crate::Entropy;
+
+/// Generates a byte sequence with uniformly distributed length.
+///
+/// Unlike [gen_bytes](crate::gen_bytes), which is biased toward short sequences,
+/// the length is uniformly distributed in `len_range`,
+/// so mid-size and long sequences are exercised as evenly as short ones.
+/// Bytes are uniformly chosen from `alphabet`, without any stopper.
+///
+/// It panics if `alphabet` is empty, or if `len_range` is empty or has no upper bound.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_uniform_len;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_bytes_uniform_len(&mut g, b"abc", 100..200);
+/// assert!((100..200).contains(&xs.len()));
+/// ```
+pub fn gen_bytes_uniform_len<E, R>(g: &mut E, alphabet: &[u8], len_range: R) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(!alphabet.is_empty(), "gen_bytes_uniform_len: alphabet must be non-empty");
+    let (min_len, max_len) = len_bounds(&len_range);
+    let max_len = max_len.expect("gen_bytes_uniform_len: the length must be bounded");
+    assert!(
+        min_len < max_len,
+        "gen_bytes_uniform_len: no length in {}..{}",
+        min_len,
+        max_len,
+    );
+    let len = gen_between(g, min_len as u64, (max_len - 1) as u64) as usize;
+    (0..len).map(|_| alphabet[g.choose_index(alphabet.len())]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn within_bounds(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_uniform_len(&mut g, b"abc", a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| b"abc".contains(x)));
+    }
+
+    #[test]
+    fn uniform() {
+        const N: usize = 16000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut hist = [0usize; 16];
+        for _ in 0..N {
+            let xs = super::gen_bytes_uniform_len(&mut g, b"a", 16..32);
+            hist[xs.len() - 16] += 1;
+        }
+        for (i, x) in hist.iter().enumerate() {
+            let freq = *x as f64 / N as f64;
+            assert!((freq - 1.0 / 16.0).abs() < 0.01, "i={}, freq={}", i, freq);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the length must be bounded")]
+    fn unbounded() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_uniform_len(&mut g, b"abc", 1..);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::Unshrinkable;
+use quickcheck::{Arbitrary, Gen};
+
+/// Sequences generated by [gen_bytes](crate::gen_bytes), shrinking within the alphabet.
+///
+/// The default shrinking of `Vec<u8>` may introduce bytes outside the alphabet.
+/// `GenBytes` remembers its alphabet and stopper,
+/// and its `shrink` only removes bytes, or replaces bytes by the first non-stopper
+/// byte of the alphabet.
+/// So every shrinked candidate consists of non-stopper bytes of the alphabet.
+///
+/// *   `arbitrary` generates over [DEFAULT_ALPHABET](Self::DEFAULT_ALPHABET)
+///     with [DEFAULT_STOPPER](Self::DEFAULT_STOPPER).
+/// *   [gen](Self::gen) generates over any alphabet.
+///
+/// ```rust
+/// use rs_quickcheck_util::GenBytes;
+/// use quickcheck::Arbitrary;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = GenBytes::gen(&mut g, b"xyz.", b'.', 1..);
+/// for x in xs.shrink() {
+///     assert!(x.data().iter().all(|c| b"xyz".contains(c)));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenBytes {
+    alphabet: Vec<u8>,
+    stopper: u8,
+    data: Vec<u8>,
+}
+
+impl GenBytes {
+    /// The alphabet used by `arbitrary`.
+    pub const DEFAULT_ALPHABET: &'static [u8] = b"abcdefghijklmno.";
+    /// The stopper used by `arbitrary`.
+    pub const DEFAULT_STOPPER: u8 = b'.';
+
+    /// Generates a sequence by [gen_bytes](crate::gen_bytes).
+    pub fn gen<R>(g: &mut Gen, alphabet: &[u8], stopper: u8, len_range: R) -> Self
+    where R: std::ops::RangeBounds<usize>
+    {
+        Self {
+            alphabet: alphabet.to_vec(),
+            stopper,
+            data: crate::gen_bytes(g, alphabet, stopper, len_range),
+        }
+    }
+
+    pub fn alphabet(&self) -> &[u8] {
+        &self.alphabet
+    }
+
+    pub fn stopper(&self) -> u8 {
+        self.stopper
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+
+    fn with_data(&self, data: Vec<u8>) -> Self {
+        Self {
+            alphabet: self.alphabet.clone(),
+            stopper: self.stopper,
+            data,
+        }
+    }
+}
+
+impl Arbitrary for GenBytes {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::gen(g, Self::DEFAULT_ALPHABET, Self::DEFAULT_STOPPER, ..)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let xs: Vec<_> = self.data.iter().map(|x| Unshrinkable::new(*x)).collect();
+        let me = self.clone();
+        let removed = xs.shrink().map(move |xs| {
+            me.with_data(xs.into_iter().map(|x| x.take()).collect())
+        });
+        let me = self.clone();
+        let simplest = self.alphabet.iter().copied().find(|x| *x != self.stopper);
+        let replaced = (0..self.data.len())
+            .filter_map(move |i| {
+                let simplest = simplest?;
+                if me.data[i] == simplest {
+                    return None;
+                }
+                let mut data = me.data.clone();
+                data[i] = simplest;
+                Some(me.with_data(data))
+            });
+        Box::new(removed.chain(replaced))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GenBytes;
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn check(xs: &GenBytes) {
+        assert!(
+            xs.data().iter().all(|x| xs.alphabet().contains(x) && *x != xs.stopper()),
+            "{:?}",
+            xs,
+        );
+    }
+
+    #[quickcheck]
+    fn shrink_within_alphabet(trial: GenBytes) {
+        check(&trial);
+        for x in trial.shrink() {
+            check(&x);
+        }
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs = GenBytes::gen(&mut g, b"\x80\xff.", b'.', 4..);
+        check(&xs);
+        for x in xs.shrink() {
+            check(&x);
+        }
+        assert!(xs.shrink().any(|x| x.data() == b"\x80\x80\x80\x80" || x.data().len() < 4));
+    }
+}
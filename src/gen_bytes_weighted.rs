@@ -0,0 +1,104 @@
+use crate::gen_bytes::len_bounds;
+use crate::{gen_len, Entropy};
+
+/// [gen_bytes](crate::gen_bytes) with explicitly weighted characters.
+///
+/// Each entry of `alphabet` is a byte and its weight.
+/// A byte is chosen with possibility proportional to its weight,
+/// just as if it were duplicated by its weight in an alphabet of [gen_bytes](crate::gen_bytes).
+/// So the effective stop possibility is
+/// $p = w_s / W$,
+/// where $w_s$ is the total weight of entries of `stopper`,
+/// and $W$ is the total weight of all entries.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_bytes_weighted;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// // mostly ASCII letters with rare control bytes
+/// let xs = gen_bytes_weighted(&mut g, &[(b'a', 50), (b'b', 40), (0x07, 1), (b'.', 9)], b'.', ..);
+/// assert!(xs.iter().all(|x| b"ab\x07".contains(x)));
+/// ```
+pub fn gen_bytes_weighted<E, R>(
+    g: &mut E,
+    alphabet: &[(u8, u32)],
+    stopper: u8,
+    len_range: R,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    let (chars, weights): (Vec<u8>, Vec<u32>) = alphabet.iter()
+        .copied()
+        .filter(|(x, _)| *x != stopper)
+        .unzip();
+    let stop_weight: u64 = alphabet.iter()
+        .filter(|(x, _)| *x == stopper)
+        .map(|(_, w)| u64::from(*w))
+        .sum();
+    let char_weight: u64 = weights.iter().map(|w| u64::from(*w)).sum();
+    let total = stop_weight + char_weight;
+    assert!(total > 0, "gen_bytes_weighted: weights cannot all be zero");
+    assert!(
+        max_len.is_some() || stop_weight > 0,
+        "gen_bytes_weighted: stopper {:?} has no weight, and the length is unbounded",
+        char::from(stopper),
+    );
+    assert!(
+        min_len == 0 || char_weight > 0,
+        "gen_bytes_weighted: no character but stopper {:?} has weight, and the minimum length is {}",
+        char::from(stopper),
+        min_len,
+    );
+    let len = gen_len(g, stop_weight as f64 / total as f64, len_range);
+    (0..len)
+        .map(|_| chars[crate::__weighted_index(g, &weights)])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    const ALPHABET: &[(u8, u32)] = &[(b'a', 6), (b'b', 3), (b'c', 1), (b'.', 2)];
+
+    #[quickcheck]
+    fn range_respected(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_weighted(&mut g, ALPHABET, b'.', a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| b"abc".contains(x)));
+    }
+
+    #[test]
+    fn frequencies() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let xs = super::gen_bytes_weighted(&mut g, ALPHABET, b'.', 10000..=10000);
+        for (ch, w) in ALPHABET[..3].iter() {
+            let freq = xs.iter().filter(|x| *x == ch).count() as f64 / xs.len() as f64;
+            let expected = f64::from(*w) / 10.0;
+            assert!((freq - expected).abs() < 0.02, "ch={}, freq={}", ch, freq);
+        }
+    }
+
+    #[test]
+    fn stop_possibility() {
+        const N: usize = 10000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let empties = (0..N)
+            .filter(|_| super::gen_bytes_weighted(&mut g, ALPHABET, b'.', ..).is_empty())
+            .count();
+        // p = 2 / 12
+        let rate = empties as f64 / N as f64;
+        assert!((rate - 2.0 / 12.0).abs() < 0.02, "rate={}", rate);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::gen_bytes::{check_alphabet, len_bounds, split_alphabet};
+use crate::random::{gen_below, gen_ratio};
+use crate::{gen_len, Entropy};
+
+/// Generates a sequence containing duplicated windows.
+///
+/// Deduplicating storage engines key on repeated content windows.
+/// This function works like [gen_bytes](crate::gen_bytes),
+/// i.e., with the same alphabet, stopper and length range,
+/// except that at each position, it copies a previous `window`-sized chunk
+/// with possibility `dup_prob_num / dup_prob_den`
+/// instead of generating a fresh byte.
+///
+/// Chunks are only copied when there are enough bytes before the position
+/// and enough room after it, so every copy produces an exact duplicate window.
+///
+/// It panics on invalid alphabets, just like [gen_bytes](crate::gen_bytes).
+pub fn gen_bytes_with_dupes<E, R>(
+    g: &mut E,
+    alphabet: &[u8],
+    stopper: u8,
+    len_range: R,
+    window: usize,
+    dup_prob_num: u32,
+    dup_prob_den: u32,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(window > 0, "gen_bytes_with_dupes: window must be positive");
+    let (min_len, max_len) = len_bounds(&len_range);
+    check_alphabet("gen_bytes_with_dupes", alphabet, stopper, min_len, max_len);
+    let (p, chars) = split_alphabet(alphabet, &[stopper]);
+    let len = gen_len(g, p, len_range);
+    let mut res = Vec::with_capacity(len);
+    while res.len() < len {
+        let pos = res.len();
+        if pos >= window && pos + window <= len && gen_ratio(g, dup_prob_num, dup_prob_den) {
+            let from = gen_below(g, (pos - window + 1) as u64) as usize;
+            res.extend_from_within(from..from + window);
+            continue;
+        }
+        res.push(chars[g.choose_index(chars.len())]);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    const STOPPER: u8 = b'.';
+    const ALPHABET: &[u8] = b"0123456789abcdef.";
+    const WINDOW: usize = 8;
+
+    #[quickcheck]
+    fn length_and_alphabet(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_bytes_with_dupes(&mut g, ALPHABET, STOPPER, a..=b, WINDOW, 1, 4);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+        assert!(xs.iter().all(|x| ALPHABET.contains(x)));
+        assert!(xs.iter().all(|x| *x != STOPPER));
+    }
+
+    /// Scans greedily for windows duplicating earlier ones.
+    /// Returns the number of duplicated windows and the number of scanning steps.
+    fn scan_dupes(xs: &[u8]) -> (usize, usize) {
+        let mut dupes = 0;
+        let mut steps = 0;
+        let mut i = 0;
+        while i + WINDOW <= xs.len() {
+            steps += 1;
+            let w = &xs[i..i + WINDOW];
+            if (0..i).any(|j| &xs[j..j + WINDOW] == w) {
+                dupes += 1;
+                i += WINDOW;
+            } else {
+                i += 1;
+            }
+        }
+        (dupes, steps)
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in alphabet")]
+    fn no_stopper() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_with_dupes(&mut g, b"ab", STOPPER, .., WINDOW, 1, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no character but stopper")]
+    fn only_stoppers() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_bytes_with_dupes(&mut g, b"..", STOPPER, 1.., WINDOW, 1, 4);
+    }
+
+    fn dup_rate(num: u32, den: u32) -> f64 {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut dupes = 0;
+        let mut steps = 0;
+        for _ in 0..50 {
+            let xs = super::gen_bytes_with_dupes(&mut g, ALPHABET, STOPPER, 400..=400, WINDOW, num, den);
+            let (d, s) = scan_dupes(&xs);
+            dupes += d;
+            steps += s;
+        }
+        dupes as f64 / steps as f64
+    }
+
+    #[test]
+    fn rate() {
+        assert!(dup_rate(0, 1) < 0.01);
+        let r = dup_rate(1, 4);
+        assert!((0.2..0.3).contains(&r), "rate={}", r);
+    }
+}
@@ -0,0 +1,92 @@
+use quickcheck::Gen;
+
+/// Method forms of generators of this crate, on [Gen].
+///
+/// Each method is exactly the free function of the same name.
+///
+/// ```rust
+/// use rs_quickcheck_util::GenExt;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = g.gen_bytes(b"abc.", b'.', 1..);
+/// let ys = g.shuffled(&xs);
+/// assert_eq!(xs.len(), ys.len());
+/// let x = g.choose_weighted(&[("valid", 9), ("malformed", 1)]);
+/// assert!(x.is_some());
+/// ```
+pub trait GenExt {
+    /// Please refer to [gen_bytes](crate::gen_bytes).
+    fn gen_bytes<R>(&mut self, alphabet: &[u8], stopper: u8, len_range: R) -> Vec<u8>
+    where R: std::ops::RangeBounds<usize>;
+
+    /// Please refer to [gen_len](crate::gen_len).
+    fn gen_len<R>(&mut self, p: f64, len_range: R) -> usize
+    where R: std::ops::RangeBounds<usize>;
+
+    /// Please refer to [shuffle](crate::shuffle).
+    fn shuffle<T>(&mut self, xs: &mut [T]);
+
+    /// Please refer to [shuffled](crate::shuffled).
+    fn shuffled<T: Clone>(&mut self, xs: &[T]) -> Vec<T>;
+
+    /// Please refer to [choose_weighted](crate::choose_weighted).
+    fn choose_weighted<'a, T>(&mut self, items: &'a [(T, u32)]) -> Option<&'a T>;
+}
+
+impl GenExt for Gen {
+    fn gen_bytes<R>(&mut self, alphabet: &[u8], stopper: u8, len_range: R) -> Vec<u8>
+    where R: std::ops::RangeBounds<usize>
+    {
+        crate::gen_bytes(self, alphabet, stopper, len_range)
+    }
+
+    fn gen_len<R>(&mut self, p: f64, len_range: R) -> usize
+    where R: std::ops::RangeBounds<usize>
+    {
+        crate::gen_len(self, p, len_range)
+    }
+
+    fn shuffle<T>(&mut self, xs: &mut [T]) {
+        crate::shuffle(self, xs)
+    }
+
+    fn shuffled<T: Clone>(&mut self, xs: &[T]) -> Vec<T> {
+        crate::shuffled(self, xs)
+    }
+
+    fn choose_weighted<'a, T>(&mut self, items: &'a [(T, u32)]) -> Option<&'a T> {
+        crate::choose_weighted(self, items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GenExt;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn same_as_free_functions(seed: u64, xs: Vec<u32>) {
+        let mut g0 = quickcheck::Gen::from_size_and_seed(32, seed);
+        let mut g1 = quickcheck::Gen::from_size_and_seed(32, seed);
+
+        assert_eq!(
+            crate::gen_bytes(&mut g0, b"abc.", b'.', ..),
+            g1.gen_bytes(b"abc.", b'.', ..),
+        );
+        assert_eq!(crate::gen_len(&mut g0, 0.25, 1..), g1.gen_len(0.25, 1..));
+
+        let mut ys0 = xs.clone();
+        let mut ys1 = xs.clone();
+        crate::shuffle(&mut g0, &mut ys0);
+        g1.shuffle(&mut ys1);
+        assert_eq!(ys0, ys1);
+
+        assert_eq!(crate::shuffled(&mut g0, &xs), g1.shuffled(&xs));
+
+        let items = [(1u8, 3), (2, 0), (3, 5)];
+        assert_eq!(
+            crate::choose_weighted(&mut g0, &items),
+            g1.choose_weighted(&items),
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use crate::random::gen_unit_f64;
+use crate::Entropy;
+use quickcheck::{Arbitrary, Gen};
+use std::ops::Range;
+
+/// Generates a finite `f64` uniformly distributed in `range`, i.e., $[start, end)$.
+///
+/// It never generates NaN or infinities.
+/// It panics if `range` is empty, or either bound is not finite.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_f64_in_range;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let x = gen_f64_in_range(&mut g, -1.0..1.0);
+/// assert!((-1.0..1.0).contains(&x));
+/// ```
+pub fn gen_f64_in_range<E>(g: &mut E, range: Range<f64>) -> f64
+where E: Entropy + ?Sized
+{
+    assert!(
+        range.start.is_finite() && range.end.is_finite(),
+        "gen_f64_in_range: {:?} is not finite",
+        range,
+    );
+    assert!(range.start < range.end, "gen_f64_in_range: {:?} is empty", range);
+    loop {
+        let u = gen_unit_f64(g);
+        // never overflows, unlike `start + (end - start) * u`
+        let x = range.start * (1.0 - u) + range.end * u;
+        // rounding may hit bounds
+        if range.contains(&x) {
+            return x;
+        }
+    }
+}
+
+/// Finite floats which stay within their range on shrinking.
+///
+/// *   `arbitrary` generates over a range with arbitrary integral bounds.
+/// *   [gen](Self::gen) generates over any range by [gen_f64_in_range].
+/// *   `shrink` moves toward the start of the range,
+///     halving the distance, and then the start itself.
+///     So every shrinked candidate is in the range.
+///
+/// ```rust
+/// use rs_quickcheck_util::F64InRange;
+/// use quickcheck::Arbitrary;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let x = F64InRange::gen(&mut g, 0.5..2.0);
+/// for y in x.shrink() {
+///     assert!((0.5..2.0).contains(&y.value()));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct F64InRange {
+    start: f64,
+    end: f64,
+    value: f64,
+}
+
+impl F64InRange {
+    /// Generates a float by [gen_f64_in_range].
+    pub fn gen<E: Entropy + ?Sized>(g: &mut E, range: Range<f64>) -> Self {
+        let value = gen_f64_in_range(g, range.clone());
+        Self {
+            start: range.start,
+            end: range.end,
+            value,
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn bounds(&self) -> Range<f64> {
+        self.start..self.end
+    }
+
+    pub fn into_inner(self) -> f64 {
+        self.value
+    }
+}
+
+impl Arbitrary for F64InRange {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let a = f64::from(i16::arbitrary(g));
+        let b = f64::from(i16::arbitrary(g));
+        let (start, end) = if a < b {
+            (a, b)
+        } else {
+            (b, a + 1.0)
+        };
+        Self::gen(g, start..end)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let me = *self;
+        let half = me.start + (me.value - me.start) / 2.0;
+        let candidates = [half, me.start];
+        let mut last = me.value;
+        let mut res = vec![];
+        for x in candidates.iter() {
+            if *x < last && me.bounds().contains(x) {
+                res.push(Self { value: *x, ..me });
+                last = *x;
+            }
+        }
+        Box::new(res.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::F64InRange;
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn finite_and_within(a: i32, b: i32, scale: u8) {
+        let scale = 2f64.powi(i32::from(scale) * 4 - 512);
+        let (a, b) = (f64::from(a) * scale, f64::from(b) * scale);
+        let (a, b) = if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        if a == b {
+            return;
+        }
+        let mut g = quickcheck::Gen::new(32);
+        for _ in 0..16 {
+            let x = super::gen_f64_in_range(&mut g, a..b);
+            assert!(x.is_finite());
+            assert!((a..b).contains(&x), "left={}, right={}, x={}", a, b, x);
+        }
+    }
+
+    #[test]
+    fn extreme_bounds() {
+        let mut g = quickcheck::Gen::new(32);
+        for _ in 0..100 {
+            let x = super::gen_f64_in_range(&mut g, f64::MIN..f64::MAX);
+            assert!(x.is_finite());
+        }
+        let tiny = f64::from_bits(1);
+        assert_eq!(super::gen_f64_in_range(&mut g, 0.0..tiny), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not finite")]
+    fn infinite() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_f64_in_range(&mut g, 0.0..f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn empty() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_f64_in_range(&mut g, 1.0..1.0);
+    }
+
+    fn check(x: &F64InRange) {
+        assert!(x.value().is_finite());
+        assert!(x.bounds().contains(&x.value()), "{:?}", x);
+    }
+
+    #[quickcheck]
+    fn shrink_within_range(x: F64InRange) {
+        check(&x);
+        for y in x.shrink() {
+            check(&y);
+            assert_eq!(y.bounds(), x.bounds());
+            assert!(y.value() < x.value());
+        }
+        if x.value() > x.bounds().start {
+            assert_eq!(x.shrink().last().map(|y| y.value()), Some(x.bounds().start));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::{gen_below, gen_u64};
+use crate::Entropy;
+
+const HEX_DIGITS: &[u8] = b"0123456789abcdef";
+
+/// Generates bytes, along with their lowercase hex encoding.
+///
+/// The number of bytes is exponentially distributed with stop possibility 1/16,
+/// so `len_range` counts bytes, and the hex string is twice as long.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+/// Bytes are arbitrary, i.e., uniformly distributed in `0..=255`.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_hex_bytes;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let (bytes, hex) = gen_hex_bytes(&mut g, 2..=2);
+/// assert_eq!(hex, format!("{:02x}{:02x}", bytes[0], bytes[1]));
+/// ```
+pub fn gen_hex_bytes<E, R>(g: &mut E, len_range: R) -> (Vec<u8>, String)
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let len = gen_len_by(g, len_range, |g| gen_below(g, 16) == 0);
+    let bytes: Vec<u8> = (0..len).map(|_| gen_u64(g) as u8).collect();
+    let mut hex = String::with_capacity(len * 2);
+    for x in bytes.iter() {
+        hex.push(char::from(HEX_DIGITS[usize::from(x >> 4)]));
+        hex.push(char::from(HEX_DIGITS[usize::from(x & 0xf)]));
+    }
+    (bytes, hex)
+}
+
+/// Generates a lowercase hex string of even length, which always decodes cleanly.
+///
+/// It is the hex part of [gen_hex_bytes],
+/// so `len_range` counts decoded bytes rather than hex digits.
+pub fn gen_hex<E, R>(g: &mut E, len_range: R) -> String
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    gen_hex_bytes(g, len_range).1
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    fn decode(hex: &str) -> Vec<u8> {
+        assert_eq!(hex.len() % 2, 0, "{:?}", hex);
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[quickcheck]
+    fn round_trip(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let (bytes, hex) = super::gen_hex_bytes(&mut g, a..=b);
+        assert!(hex.bytes().all(|c| super::HEX_DIGITS.contains(&c)), "{:?}", hex);
+        assert_eq!(decode(&hex), bytes);
+        assert!(bytes.len() >= a, "left={}, right={}", bytes.len(), a);
+        assert!(bytes.len() <= b, "left={}, right={}", bytes.len(), b);
+    }
+
+    #[quickcheck]
+    fn even_length(seed: u64) {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+        let hex = super::gen_hex(&mut g, ..);
+        assert_eq!(hex.len() % 2, 0, "{:?}", hex);
+        decode(&hex);
+    }
+}
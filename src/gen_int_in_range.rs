@@ -0,0 +1,190 @@
+use crate::random::{gen_below_u128, gen_u64};
+use crate::shrink_towards::{distance, step};
+use crate::{shrink_towards, Entropy, PrimitiveInt};
+use quickcheck::{Arbitrary, Gen};
+use std::ops::{Range, RangeInclusive};
+
+/// Generates an integer uniformly distributed in `range`.
+///
+/// It never generates out-of-range values, and there is no modulo bias.
+/// It panics if `range` is empty.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_int_in_range;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let x = gen_int_in_range(&mut g, -3i32..5);
+/// assert!((-3..5).contains(&x));
+/// ```
+pub fn gen_int_in_range<E, T>(g: &mut E, range: Range<T>) -> T
+where
+    E: Entropy + ?Sized,
+    T: PrimitiveInt,
+{
+    assert!(
+        range.start < range.end,
+        "gen_int_in_range: {:?} is empty",
+        range,
+    );
+    let d = distance(range.start, range.end);
+    step(range.start, range.end, gen_below_u128(g, d))
+}
+
+/// Generates an integer uniformly distributed in $[lo, hi]$.
+fn gen_int_between<E, T>(g: &mut E, lo: T, hi: T) -> T
+where
+    E: Entropy + ?Sized,
+    T: PrimitiveInt,
+{
+    match distance(lo, hi).checked_add(1) {
+        Some(n) => step(lo, hi, gen_below_u128(g, n)),
+        None => T::__from_bits((u128::from(gen_u64(g)) << 64) | u128::from(gen_u64(g))),
+    }
+}
+
+/// Integers which stay within their range on shrinking.
+///
+/// The default shrinking of integers moves toward zero, which may be out of range.
+/// `IntInRange` remembers its range, and its `shrink` moves toward the start of
+/// the range by [shrink_towards].
+/// So every shrinked candidate is in the range.
+///
+/// *   `arbitrary` generates over a range with arbitrary bounds.
+/// *   [gen](Self::gen) generates over any range by [gen_int_in_range].
+///
+/// ```rust
+/// use rs_quickcheck_util::IntInRange;
+/// use quickcheck::Arbitrary;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let x = IntInRange::gen(&mut g, 1024u16..2048);
+/// for y in x.shrink() {
+///     assert!((1024..2048).contains(&y.value()));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntInRange<T> {
+    start: T,
+    last: T,
+    value: T,
+}
+
+impl<T: PrimitiveInt> IntInRange<T> {
+    /// Generates an integer by [gen_int_in_range].
+    pub fn gen<E: Entropy + ?Sized>(g: &mut E, range: Range<T>) -> Self {
+        let value = gen_int_in_range(g, range.clone());
+        let last = step(range.end, range.start, 1);
+        Self {
+            start: range.start,
+            last,
+            value,
+        }
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// The range, with its end included.
+    pub fn bounds(&self) -> RangeInclusive<T> {
+        self.start..=self.last
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PrimitiveInt + Arbitrary> Arbitrary for IntInRange<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let a = T::arbitrary(g);
+        let b = T::arbitrary(g);
+        let (start, last) = if a <= b { (a, b) } else { (b, a) };
+        Self {
+            start,
+            last,
+            value: gen_int_between(g, start, last),
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let me = *self;
+        Box::new(shrink_towards(self.value, self.start).map(move |value| Self { value, ..me }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntInRange;
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn within_range(a: i16, b: i16) {
+        let (a, b) = if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        if a == b {
+            return;
+        }
+        let mut g = quickcheck::Gen::new(32);
+        for _ in 0..16 {
+            let x = super::gen_int_in_range(&mut g, a..b);
+            assert!((a..b).contains(&x), "left={}, right={}, x={}", a, b, x);
+        }
+    }
+
+    #[test]
+    fn both_ends_reached() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            seen[super::gen_int_in_range(&mut g, 0usize..4)] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+
+    #[test]
+    fn full_width() {
+        let mut g = quickcheck::Gen::new(32);
+        let x = super::gen_int_in_range(&mut g, i128::MIN..i128::MAX);
+        assert!(x < i128::MAX);
+        let x = super::gen_int_in_range(&mut g, u128::MAX - 1..u128::MAX);
+        assert_eq!(x, u128::MAX - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn empty() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_int_in_range(&mut g, 3u8..3);
+    }
+
+    fn check<T: super::PrimitiveInt>(x: &IntInRange<T>) {
+        assert!(x.bounds().contains(&x.value()), "{:?}", x);
+    }
+
+    #[quickcheck]
+    fn shrink_within_range(x: IntInRange<i32>) {
+        check(&x);
+        for y in x.shrink() {
+            check(&y);
+            assert_eq!(y.bounds(), x.bounds());
+        }
+    }
+
+    #[test]
+    fn shrink_towards_start() {
+        let mut g = quickcheck::Gen::new(32);
+        let x = IntInRange::gen(&mut g, 100u8..200);
+        check(&x);
+        for y in x.shrink() {
+            check(&y);
+        }
+        if x.value() != 100 {
+            assert_eq!(x.shrink().last().map(|y| y.value()), Some(100));
+        }
+    }
+}
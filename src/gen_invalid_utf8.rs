@@ -0,0 +1,114 @@
+use crate::gen_bytes::{gen_len_by, len_bounds};
+use crate::random::{gen_below, gen_between};
+use crate::Entropy;
+use std::ops::Bound;
+
+/// Generates a byte sequence which is *not* valid UTF-8.
+///
+/// UTF-8 decoders must handle malformed sequences gracefully,
+/// but malformed sequences are surprisingly hard to get by chance.
+/// The output consists of ASCII characters
+/// with one malformed sequence at a random position, which is one of
+///
+/// *   a lone continuation byte,
+/// *   an overlong encoding,
+/// *   an encoded surrogate,
+/// *   a truncated multi-byte sequence,
+/// *   a byte never appearing in UTF-8, i.e., `0xF5..=0xFF`.
+///
+/// The length, in bytes, is exponentially distributed with stop possibility 1/16.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+/// As the malformed sequence takes at least one byte,
+/// the lower bound of `len_range` is raised to 1 if it is smaller.
+/// It panics if `len_range` allows no positive length.
+pub fn gen_invalid_utf8<E, R>(g: &mut E, len_range: R) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    let min_len = min_len.max(1);
+    assert!(
+        max_len.map_or(true, |n| n > min_len),
+        "gen_invalid_utf8: {:?} allows no positive length",
+        (len_range.start_bound(), len_range.end_bound()),
+    );
+    let max_len = max_len.map_or(Bound::Unbounded, Bound::Excluded);
+    let len = gen_len_by(g, (Bound::Included(min_len), max_len), |g| gen_below(g, 16) == 0);
+    let malformed = loop {
+        let x = gen_malformed(g);
+        if x.len() <= len {
+            break x;
+        }
+    };
+    let mut res: Vec<u8> = (0..len).map(|_| gen_between(g, 0, 0x7f) as u8).collect();
+    let pos = gen_below(g, (len - malformed.len() + 1) as u64) as usize;
+    res[pos..pos + malformed.len()].copy_from_slice(&malformed);
+    res
+}
+
+fn gen_continuation<E: Entropy + ?Sized>(g: &mut E) -> u8 {
+    gen_between(g, 0x80, 0xbf) as u8
+}
+
+fn gen_malformed<E: Entropy + ?Sized>(g: &mut E) -> Vec<u8> {
+    match gen_below(g, 8) {
+        // lone continuation byte
+        0 => vec![gen_continuation(g)],
+        // overlong 2-byte encoding
+        1 => vec![gen_between(g, 0xc0, 0xc1) as u8, gen_continuation(g)],
+        // overlong 3-byte encoding
+        2 => vec![0xe0, gen_between(g, 0x80, 0x9f) as u8, gen_continuation(g)],
+        // overlong 4-byte encoding
+        3 => vec![0xf0, gen_between(g, 0x80, 0x8f) as u8, gen_continuation(g), gen_continuation(g)],
+        // surrogate
+        4 => vec![0xed, gen_between(g, 0xa0, 0xbf) as u8, gen_continuation(g)],
+        // truncated 2-byte sequence
+        5 => vec![gen_between(g, 0xc2, 0xdf) as u8],
+        // truncated 3-byte or 4-byte sequence
+        6 => {
+            if gen_below(g, 2) == 0 {
+                vec![gen_between(g, 0xe1, 0xec) as u8, gen_continuation(g)]
+            } else {
+                vec![gen_between(g, 0xf1, 0xf3) as u8, gen_continuation(g), gen_continuation(g)]
+            }
+        }
+        // never in UTF-8
+        _ => vec![gen_between(g, 0xf5, 0xff) as u8],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn invalid(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize + 1)
+        } else {
+            (b as usize, a as usize + 1)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_invalid_utf8(&mut g, a..=b);
+        assert!(std::str::from_utf8(&xs).is_err(), "{:?}", xs);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+    }
+
+    #[test]
+    fn short() {
+        let mut g = quickcheck::Gen::new(32);
+        for _ in 0..1000 {
+            let xs = super::gen_invalid_utf8(&mut g, ..);
+            assert!(std::str::from_utf8(&xs).is_err(), "{:?}", xs);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "allows no positive length")]
+    fn no_positive_length() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_invalid_utf8(&mut g, ..1);
+    }
+}
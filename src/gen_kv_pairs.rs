@@ -0,0 +1,93 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::{gen_below, gen_ratio};
+use crate::Entropy;
+
+/// Generates key-value pairs with duplicate keys injected.
+///
+/// Parsers of key-value formats (e.g., HTTP headers, query strings) must handle duplicate keys.
+/// For each pair but the first one, with possibility `dup_prob_num / dup_prob_den`,
+/// the key is uniformly chosen from previously used keys.
+/// Otherwise, the key is generated by `key_gen`.
+/// Values are always generated by `val_gen`.
+///
+/// The number of pairs is exponentially distributed with stop possibility 1/16.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on `pair_count_range`.
+pub fn gen_kv_pairs<E, K, V, R>(
+    g: &mut E,
+    key_gen: K,
+    val_gen: V,
+    pair_count_range: R,
+    dup_prob_num: u32,
+    dup_prob_den: u32,
+) -> Vec<(Vec<u8>, Vec<u8>)>
+where
+    E: Entropy + ?Sized,
+    K: Fn(&mut E) -> Vec<u8>,
+    V: Fn(&mut E) -> Vec<u8>,
+    R: std::ops::RangeBounds<usize>,
+{
+    let n = gen_len_by(g, pair_count_range, |g| gen_below(g, 16) == 0);
+    let mut res: Vec<(Vec<u8>, Vec<u8>)> = Vec::with_capacity(n);
+    for _ in 0..n {
+        let key = if !res.is_empty() && gen_ratio(g, dup_prob_num, dup_prob_den) {
+            let i = gen_below(g, res.len() as u64) as usize;
+            res[i].0.clone()
+        } else {
+            key_gen(g)
+        };
+        let val = val_gen(g);
+        res.push((key, val));
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+    use std::cell::Cell;
+    use std::collections::HashSet;
+
+    #[test]
+    fn dup_rate() {
+        const N: usize = 10000;
+        let counter = Cell::new(0u32);
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let pairs = super::gen_kv_pairs(
+            &mut g,
+            |_| {
+                // always fresh keys
+                counter.set(counter.get() + 1);
+                counter.get().to_le_bytes().to_vec()
+            },
+            |g| crate::gen_bytes(g, b"abc.", b'.', ..),
+            N..=N,
+            1,
+            4,
+        );
+        assert_eq!(pairs.len(), N);
+        let mut seen = HashSet::new();
+        let dups = pairs.iter().filter(|(k, _)| !seen.insert(k.clone())).count();
+        let rate = dups as f64 / (N - 1) as f64;
+        assert!((rate - 0.25).abs() < 0.02, "rate={}", rate);
+    }
+
+    #[quickcheck]
+    fn range_respected(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let pairs = super::gen_kv_pairs(
+            &mut g,
+            |g| crate::gen_bytes(g, b"abc.", b'.', 1..),
+            |g| crate::gen_bytes(g, b"xyz.", b'.', ..),
+            a..=b,
+            1,
+            2,
+        );
+        assert!(pairs.len() >= a, "left={}, right={}", pairs.len(), a);
+        assert!(pairs.len() <= b, "left={}, right={}", pairs.len(), b);
+    }
+}
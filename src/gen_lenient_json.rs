@@ -0,0 +1,196 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::{gen_below, gen_ratio};
+use crate::Entropy;
+
+const LENIENT_NUM: u32 = 1;
+const LENIENT_DEN: u32 = 8;
+
+/// Generates almost-valid JSON text for testing lenient JSON parsers.
+///
+/// It generates a JSON-shaped structure, nested at most `max_depth` levels of
+/// arrays and objects, and then injects common non-standard features,
+/// each with possibility 1/8 where applicable:
+///
+/// *   trailing commas in non-empty arrays and objects,
+/// *   `//` line comments before values,
+/// *   single-quoted strings,
+/// *   unquoted object keys.
+///
+/// Strings and keys consist of lowercase ASCII letters only,
+/// so no escaping is involved.
+/// Numbers, arrays and objects have exponentially distributed sizes.
+///
+/// It is available with the `serde_json` feature,
+/// as it is meant to be checked against `serde_json`, a strict parser.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_lenient_json;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let json = gen_lenient_json(&mut g, 3);
+/// assert!(!json.is_empty());
+/// ```
+pub fn gen_lenient_json<E>(g: &mut E, max_depth: usize) -> String
+where E: Entropy + ?Sized
+{
+    let mut res = String::new();
+    gen_value(g, max_depth, &mut res);
+    res
+}
+
+fn lenient<E: Entropy + ?Sized>(g: &mut E) -> bool {
+    gen_ratio(g, LENIENT_NUM, LENIENT_DEN)
+}
+
+fn stop<E: Entropy + ?Sized>(g: &mut E) -> bool {
+    gen_below(g, 4) == 0
+}
+
+fn gen_value<E: Entropy + ?Sized>(g: &mut E, depth: usize, out: &mut String) {
+    if lenient(g) {
+        out.push_str("// ");
+        gen_word(g, out);
+        out.push('\n');
+    }
+    let kinds = if depth == 0 { 5 } else { 7 };
+    match gen_below(g, kinds) {
+        0 => out.push_str("null"),
+        1 => out.push_str("true"),
+        2 => out.push_str("false"),
+        3 => gen_number(g, out),
+        4 => gen_quoted(g, out),
+        5 => gen_array(g, depth - 1, out),
+        _ => gen_object(g, depth - 1, out),
+    }
+}
+
+fn gen_word<E: Entropy + ?Sized>(g: &mut E, out: &mut String) {
+    let len = gen_len_by(g, .., stop);
+    for _ in 0..len {
+        out.push(char::from(b'a' + gen_below(g, 26) as u8));
+    }
+}
+
+fn gen_number<E: Entropy + ?Sized>(g: &mut E, out: &mut String) {
+    if gen_ratio(g, 1, 2) {
+        out.push('-');
+    }
+    let len = gen_len_by(g, 1.., stop);
+    out.push(char::from(b'1' + gen_below(g, 9) as u8));
+    for _ in 1..len {
+        out.push(char::from(b'0' + gen_below(g, 10) as u8));
+    }
+}
+
+fn gen_quoted<E: Entropy + ?Sized>(g: &mut E, out: &mut String) {
+    let quote = if lenient(g) { '\'' } else { '"' };
+    out.push(quote);
+    gen_word(g, out);
+    out.push(quote);
+}
+
+fn gen_array<E: Entropy + ?Sized>(g: &mut E, depth: usize, out: &mut String) {
+    out.push('[');
+    let len = gen_len_by(g, .., stop);
+    for i in 0..len {
+        if i > 0 {
+            out.push(',');
+        }
+        gen_value(g, depth, out);
+    }
+    if len > 0 && lenient(g) {
+        out.push(',');
+    }
+    out.push(']');
+}
+
+fn gen_object<E: Entropy + ?Sized>(g: &mut E, depth: usize, out: &mut String) {
+    out.push('{');
+    let len = gen_len_by(g, .., stop);
+    for i in 0..len {
+        if i > 0 {
+            out.push(',');
+        }
+        if lenient(g) {
+            out.push('k');
+            gen_word(g, out);
+        } else {
+            gen_quoted(g, out);
+        }
+        out.push(':');
+        gen_value(g, depth, out);
+    }
+    if len > 0 && lenient(g) {
+        out.push(',');
+    }
+    out.push('}');
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    /// Returns whether brackets and braces are balanced outside strings and comments,
+    /// and the maximal nesting depth.
+    fn balanced(json: &str) -> Option<usize> {
+        let mut stack = vec![];
+        let mut max_depth = 0;
+        let mut quote = None;
+        let mut comment = false;
+        for c in json.chars() {
+            if comment {
+                comment = c != '\n';
+                continue;
+            }
+            if let Some(q) = quote {
+                if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+            match c {
+                '"' | '\'' => quote = Some(c),
+                '/' => comment = true,
+                '[' | '{' => {
+                    stack.push(c);
+                    max_depth = max_depth.max(stack.len());
+                }
+                ']' | '}' => {
+                    let open = if c == ']' { '[' } else { '{' };
+                    if stack.pop() != Some(open) {
+                        return None;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if stack.is_empty() && quote.is_none() {
+            Some(max_depth)
+        } else {
+            None
+        }
+    }
+
+    #[quickcheck]
+    fn json_shaped(depth: u8) {
+        let depth = usize::from(depth % 5);
+        let mut g = quickcheck::Gen::new(32);
+        let json = super::gen_lenient_json(&mut g, depth);
+        let d = balanced(&json);
+        assert!(d.map_or(false, |d| d <= depth), "{}", json);
+    }
+
+    #[test]
+    fn sometimes_rejected_by_strict_parsers() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let samples: Vec<_> = (0..1000)
+            .map(|_| super::gen_lenient_json(&mut g, 3))
+            .collect();
+        let n = samples
+            .iter()
+            .filter(|x| serde_json::from_str::<serde_json::Value>(x).is_err())
+            .count();
+        assert!(n > 100, "n={}", n);
+        assert!(n < 1000, "n={}", n);
+    }
+}
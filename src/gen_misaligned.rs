@@ -0,0 +1,60 @@
+use crate::random::gen_between;
+use quickcheck::Gen;
+
+/// Generates a body behind a random number of filler bytes, to test misaligned parsing.
+///
+/// Zero-copy parsers sometimes assume alignment.
+/// This function prepends `0..=max_pad` filler bytes, uniformly chosen,
+/// before a body generated by `body_gen`.
+/// It returns the bytes and the offset where the body begins.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, gen_misaligned};
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let (bytes, offset) = gen_misaligned(&mut g, |g| gen_bytes(g, b"abc.", b'.', ..), 7);
+/// assert!(offset <= 7);
+/// assert!(bytes[offset..].iter().all(|x| b"abc".contains(x)));
+/// ```
+pub fn gen_misaligned<F>(g: &mut Gen, body_gen: F, max_pad: usize) -> (Vec<u8>, usize)
+where F: Fn(&mut Gen) -> Vec<u8>
+{
+    let pad = gen_between(g, 0, max_pad as u64) as usize;
+    let mut res: Vec<u8> = (0..pad).map(|_| gen_between(g, 0, 255) as u8).collect();
+    res.extend(body_gen(g));
+    (res, pad)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn body_at_offset(max_pad: u8) {
+        let max_pad = usize::from(max_pad);
+        let mut g = quickcheck::Gen::new(32);
+        let body = std::cell::RefCell::new(vec![]);
+        let (bytes, offset) = super::gen_misaligned(
+            &mut g,
+            |g| {
+                let xs = crate::gen_bytes(g, b"abc.", b'.', ..);
+                *body.borrow_mut() = xs.clone();
+                xs
+            },
+            max_pad,
+        );
+        assert!(offset <= max_pad, "left={}, right={}", offset, max_pad);
+        assert_eq!(bytes[offset..], body.borrow()[..]);
+    }
+
+    #[test]
+    fn covers_offsets() {
+        let mut g = quickcheck::Gen::new(32);
+        let offsets: Vec<_> = (0..100)
+            .map(|_| super::gen_misaligned(&mut g, |_| vec![], 3).1)
+            .collect();
+        for x in 0..=3 {
+            assert!(offsets.contains(&x), "{}", x);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::Entropy;
+
+/// Generates a sequence with randomly mixed letter cases.
+///
+/// Characters are chosen from `base`, and every ASCII letter is randomly
+/// upper-cased or lower-cased.
+/// Other characters are kept untouched.
+/// It returns both the mixed-case sequence and its canonical (lower-cased) form,
+/// which is useful to fuzz case-insensitive matching.
+///
+/// The length is exponentially distributed, as if `base` were followed by
+/// one more stopper character.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_mixed_case;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let (mixed, canonical) = gen_mixed_case(&mut g, b"abc", 1..);
+/// assert_eq!(mixed.to_ascii_lowercase(), canonical);
+/// ```
+pub fn gen_mixed_case<E, R>(
+    g: &mut E,
+    base: &[u8],
+    len_range: R,
+) -> (Vec<u8>, Vec<u8>)
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(!base.is_empty(), "gen_mixed_case: base must be non-empty");
+    let stop_den = base.len() as u64 + 1;
+    let len = gen_len_by(g, len_range, |g| gen_below(g, stop_den) == 0);
+    let mut mixed = Vec::with_capacity(len);
+    let mut canonical = Vec::with_capacity(len);
+    for _ in 0..len {
+        let ch = base[g.choose_index(base.len())];
+        if gen_below(g, 2) == 0 {
+            mixed.push(ch.to_ascii_uppercase());
+        } else {
+            mixed.push(ch.to_ascii_lowercase());
+        }
+        canonical.push(ch.to_ascii_lowercase());
+    }
+    (mixed, canonical)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn canonical(a: u8, b: u8) {
+        const BASE: &[u8] = b"aBcD-";
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let (mixed, canonical) = super::gen_mixed_case(&mut g, BASE, a..=b);
+        assert!(mixed.len() >= a, "left={}, right={}", mixed.len(), a);
+        assert!(mixed.len() <= b, "left={}, right={}", mixed.len(), b);
+        assert_eq!(mixed.to_ascii_lowercase(), canonical);
+        assert!(canonical.iter().all(|x| BASE.to_ascii_lowercase().contains(x)));
+    }
+
+    #[test]
+    fn cases_are_mixed() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs: Vec<u8> = (0..100)
+            .flat_map(|_| super::gen_mixed_case(&mut g, b"a", 1..).0)
+            .collect();
+        assert!(xs.contains(&b'a'));
+        assert!(xs.contains(&b'A'));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::Entropy;
+
+/// Generates palindromes or near-palindromes.
+///
+/// It builds a half from `alphabet`, and mirrors it.
+/// The output is of odd length, with a random middle character, or of even length,
+/// equally likely.
+/// When `exact` is false, with possibility 1/2, one byte is perturbed to
+/// another character of `alphabet`, making it a near-miss, i.e., not a palindrome.
+/// The middle byte of an odd-length output is never perturbed,
+/// so outputs of at most 1 byte stay palindromes.
+///
+/// The length of the half is exponentially distributed, as if `alphabet` were
+/// followed by one more stopper character.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+pub fn gen_palindrome<E, R>(
+    g: &mut E,
+    alphabet: &[u8],
+    half_len_range: R,
+    exact: bool,
+) -> Vec<u8>
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(!alphabet.is_empty(), "gen_palindrome: alphabet must be non-empty");
+    let stop_den = alphabet.len() as u64 + 1;
+    let half_len = gen_len_by(g, half_len_range, |g| gen_below(g, stop_den) == 0);
+    let half: Vec<u8> = (0..half_len).map(|_| alphabet[g.choose_index(alphabet.len())]).collect();
+    let mut res = half.clone();
+    if gen_below(g, 2) == 0 {
+        res.push(alphabet[g.choose_index(alphabet.len())]);
+    }
+    res.extend(half.iter().rev());
+    if !exact && half_len > 0 && gen_below(g, 2) == 0 {
+        // any position but the middle one, whose mirror is itself
+        let j = g.choose_index(2 * half_len);
+        let i = if j < half_len { j } else { res.len() - 2 * half_len + j };
+        let others: Vec<u8> = alphabet.iter().copied().filter(|x| *x != res[i]).collect();
+        if !others.is_empty() {
+            res[i] = others[g.choose_index(others.len())];
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    const ALPHABET: &[u8] = b"abc";
+
+    fn is_palindrome(xs: &[u8]) -> bool {
+        xs.iter().eq(xs.iter().rev())
+    }
+
+    #[quickcheck]
+    fn exact(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_palindrome(&mut g, ALPHABET, a..=b, true);
+        assert!(is_palindrome(&xs), "{:?}", xs);
+        assert!(xs.len() >= 2 * a, "left={}, right={}", xs.len(), 2 * a);
+        assert!(xs.len() <= 2 * b + 1, "left={}, right={}", xs.len(), 2 * b + 1);
+        assert!(xs.iter().all(|x| ALPHABET.contains(x)));
+    }
+
+    #[test]
+    fn near_miss() {
+        let mut perturbed = 0;
+        for seed in 0..1000 {
+            // draws are the same as the exact one, except for the perturbation
+            let gen = |exact| {
+                let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+                super::gen_palindrome(&mut g, ALPHABET, ..3, exact)
+            };
+            let xs = gen(false);
+            assert!(xs.iter().all(|x| ALPHABET.contains(x)));
+            if xs != gen(true) {
+                assert!(!is_palindrome(&xs), "{:?}", xs);
+                perturbed += 1;
+            }
+        }
+        assert!(perturbed > 100, "perturbed={}", perturbed);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::random::gen_below_u128;
+use crate::Entropy;
+use std::collections::BTreeSet;
+
+/// Splits `total` into `parts` random parts summing to `total`.
+///
+/// *   If `allow_zero`, parts are nonnegative.
+/// *   Otherwise, parts are positive, so `total` must be at least `parts`.
+///
+/// Every composition, i.e., ordered sequence of parts, is equally likely.
+/// `parts` can be 0 only if `total` is 0.
+/// It costs $O(parts \log parts)$, regardless of `total`.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_partition;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// // chunk sizes to feed a 100-byte buffer in 4 reads
+/// let chunks = gen_partition(&mut g, 100, 4, false);
+/// assert_eq!(chunks.iter().sum::<usize>(), 100);
+/// assert!(chunks.iter().all(|x| *x > 0));
+/// ```
+pub fn gen_partition<E>(g: &mut E, total: usize, parts: usize, allow_zero: bool) -> Vec<usize>
+where E: Entropy + ?Sized
+{
+    if parts == 0 {
+        assert!(total == 0, "gen_partition: {} cannot be split into 0 parts", total);
+        return vec![];
+    }
+    if !allow_zero {
+        assert!(
+            total >= parts,
+            "gen_partition: {} cannot be split into {} positive parts",
+            total,
+            parts,
+        );
+    }
+    // offsets of the `parts - 1` inner boundaries, nondecreasing in `0..=total`
+    let k = parts - 1;
+    let bounds: Vec<usize> = if allow_zero {
+        // stars and bars: `k` bars among `total + k` slots, in u128 to never overflow
+        sample_distinct(g, total as u128 + k as u128, k)
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| (x - i as u128) as usize)
+            .collect()
+    } else {
+        // cut at `k` distinct gaps among the `total - 1` ones between units
+        sample_distinct(g, total as u128 - 1, k)
+            .into_iter()
+            .map(|x| x as usize + 1)
+            .collect()
+    };
+    let mut res = Vec::with_capacity(parts);
+    let mut last = 0;
+    for b in bounds.into_iter().chain(std::iter::once(total)) {
+        res.push(b - last);
+        last = b;
+    }
+    res
+}
+
+/// Uniformly chooses `k` distinct numbers in `0..n`, by Floyd's algorithm.
+fn sample_distinct<E>(g: &mut E, n: u128, k: usize) -> BTreeSet<u128>
+where E: Entropy + ?Sized
+{
+    let mut res = BTreeSet::new();
+    for j in n - k as u128..n {
+        let t = gen_below_u128(g, j + 1);
+        if !res.insert(t) {
+            res.insert(j);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn sum_and_constraint(total: u16, parts: u8, allow_zero: bool) {
+        let total = usize::from(total);
+        let parts = usize::from(parts).max(1);
+        if !allow_zero && total < parts {
+            return;
+        }
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_partition(&mut g, total, parts, allow_zero);
+        assert_eq!(xs.len(), parts);
+        assert_eq!(xs.iter().sum::<usize>(), total);
+        if !allow_zero {
+            assert!(xs.iter().all(|x| *x > 0), "{:?}", xs);
+        }
+    }
+
+    #[test]
+    fn zero_parts() {
+        let mut g = quickcheck::Gen::new(32);
+        assert!(super::gen_partition(&mut g, 0, 0, false).is_empty());
+        assert!(super::gen_partition(&mut g, 0, 0, true).is_empty());
+        assert_eq!(super::gen_partition(&mut g, 0, 3, true), vec![0, 0, 0]);
+        assert_eq!(super::gen_partition(&mut g, 3, 3, false), vec![1, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be split into 0 parts")]
+    fn nonzero_into_zero_parts() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_partition(&mut g, 1, 0, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive parts")]
+    fn too_few_to_be_positive() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_partition(&mut g, 2, 3, false);
+    }
+
+    #[test]
+    fn huge_total() {
+        let mut g = quickcheck::Gen::new(32);
+        assert_eq!(super::gen_partition(&mut g, usize::MAX, 1, true), vec![usize::MAX]);
+        let xs = super::gen_partition(&mut g, usize::MAX, 3, true);
+        assert_eq!(xs.iter().fold(0u128, |acc, x| acc + *x as u128), usize::MAX as u128);
+        let xs = super::gen_partition(&mut g, usize::MAX, 3, false);
+        assert!(xs.iter().all(|x| *x > 0), "{:?}", xs);
+        assert_eq!(xs.iter().fold(0u128, |acc, x| acc + *x as u128), usize::MAX as u128);
+    }
+
+    #[test]
+    fn uniform_compositions() {
+        // 3 into 2 nonnegative parts: 4 compositions
+        const N: usize = 8000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut hist = [0usize; 4];
+        for _ in 0..N {
+            hist[super::gen_partition(&mut g, 3, 2, true)[0]] += 1;
+        }
+        for (i, x) in hist.iter().enumerate() {
+            let freq = *x as f64 / N as f64;
+            assert!((freq - 0.25).abs() < 0.02, "i={}, freq={}", i, freq);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::Entropy;
+
+/// Generates alternating valid and invalid segments.
+///
+/// Parsers with error recovery need inputs alternating valid and garbage regions.
+/// Segments are generated alternately by `valid_gen` and `invalid_gen`,
+/// starting with either of them, equally likely.
+/// It returns the concatenated bytes and a per-segment validity mask.
+///
+/// The number of segments is exponentially distributed with stop possibility 1/16.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the range.
+pub fn gen_recoverable<E, V, I, R>(
+    g: &mut E,
+    valid_gen: V,
+    invalid_gen: I,
+    segment_count_range: R,
+) -> (Vec<u8>, Vec<bool>)
+where
+    E: Entropy + ?Sized,
+    V: Fn(&mut E) -> Vec<u8>,
+    I: Fn(&mut E) -> Vec<u8>,
+    R: std::ops::RangeBounds<usize>,
+{
+    let n = gen_len_by(g, segment_count_range, |g| gen_below(g, 16) == 0);
+    let mut valid = gen_below(g, 2) == 0;
+    let mut bytes = vec![];
+    let mut mask = Vec::with_capacity(n);
+    for _ in 0..n {
+        if valid {
+            bytes.extend(valid_gen(g));
+        } else {
+            bytes.extend(invalid_gen(g));
+        }
+        mask.push(valid);
+        valid = !valid;
+    }
+    (bytes, mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn mask_aligns(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let (bytes, mask) = super::gen_recoverable(
+            &mut g,
+            |g| crate::gen_bytes(g, b"abc.", b'.', 1..),
+            |g| crate::gen_bytes(g, b"xyz.", b'.', 1..),
+            a..=b,
+        );
+        assert!(mask.len() >= a, "left={}, right={}", mask.len(), a);
+        assert!(mask.len() <= b, "left={}, right={}", mask.len(), b);
+        assert!(mask.windows(2).all(|w| w[0] != w[1]));
+        // Segments are non-empty and alternate, so each run of the same kind is a segment.
+        let runs: Vec<bool> = bytes.iter()
+            .map(|x| b"abc".contains(x))
+            .fold(vec![], |mut runs, valid| {
+                if runs.last() != Some(&valid) {
+                    runs.push(valid);
+                }
+                runs
+            });
+        assert_eq!(runs, mask);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::random::gen_below;
+use quickcheck::Gen;
+
+/// Generates a recursive structure no deeper than `max_depth`.
+///
+/// *   `leaf` generates a leaf, whose depth is 0.
+/// *   `node` generates an internal node.
+///     Children are generated by calling its second argument,
+///     each of which is one level shallower.
+///
+/// At depth `max_depth`, i.e., the root, down to depth 1,
+/// a leaf or a node is generated with equal possibility.
+/// At depth 0, a leaf is forced, so generation always terminates.
+///
+/// The child generator takes the [Gen], which is borrowed by `node` meanwhile.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_recursive;
+/// use quickcheck::Arbitrary;
+///
+/// #[derive(Debug)]
+/// enum Expr {
+///     Lit(i32),
+///     Add(Box<Expr>, Box<Expr>),
+/// }
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let e = gen_recursive(
+///     &mut g,
+///     4,
+///     |g| Expr::Lit(i32::arbitrary(g)),
+///     |g, child| Expr::Add(Box::new(child(g)), Box::new(child(g))),
+/// );
+/// ```
+pub fn gen_recursive<T, L, N>(g: &mut Gen, max_depth: usize, leaf: L, node: N) -> T
+where
+    L: Fn(&mut Gen) -> T,
+    N: Fn(&mut Gen, &mut dyn FnMut(&mut Gen) -> T) -> T,
+{
+    gen_at(g, max_depth, &leaf, &node)
+}
+
+fn gen_at<T, L, N>(g: &mut Gen, depth: usize, leaf: &L, node: &N) -> T
+where
+    L: Fn(&mut Gen) -> T,
+    N: Fn(&mut Gen, &mut dyn FnMut(&mut Gen) -> T) -> T,
+{
+    if depth == 0 || gen_below(g, 2) == 0 {
+        return leaf(g);
+    }
+    node(g, &mut |g| gen_at(g, depth - 1, leaf, node))
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[derive(Debug)]
+    enum Tree {
+        Leaf,
+        Node(Box<Tree>, Box<Tree>),
+    }
+
+    fn depth(t: &Tree) -> usize {
+        match t {
+            Tree::Leaf => 0,
+            Tree::Node(l, r) => 1 + depth(l).max(depth(r)),
+        }
+    }
+
+    fn gen_tree(g: &mut quickcheck::Gen, max_depth: usize) -> Tree {
+        super::gen_recursive(
+            g,
+            max_depth,
+            |_| Tree::Leaf,
+            |g, child| Tree::Node(Box::new(child(g)), Box::new(child(g))),
+        )
+    }
+
+    #[quickcheck]
+    fn bounded_depth(max_depth: u8) {
+        let max_depth = usize::from(max_depth % 16);
+        let mut g = quickcheck::Gen::new(32);
+        let t = gen_tree(&mut g, max_depth);
+        assert!(depth(&t) <= max_depth, "{:?}", t);
+    }
+
+    #[test]
+    fn forced_leaf() {
+        let mut g = quickcheck::Gen::new(32);
+        assert!(matches!(gen_tree(&mut g, 0), Tree::Leaf));
+    }
+
+    #[test]
+    fn nodes_generated() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let depths: Vec<usize> = (0..100).map(|_| depth(&gen_tree(&mut g, 3))).collect();
+        assert!(depths.contains(&0));
+        assert!(depths.iter().any(|x| *x > 1));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::gen_bytes::len_bounds;
+use crate::{gen_len, Entropy};
+
+/// Generates a string with exponentially distributed length.
+///
+/// It is [gen_bytes](crate::gen_bytes) over a `char` alphabet,
+/// so the result is always valid UTF-8.
+/// The stop possibility is the number of stoppers in the alphabet over its size,
+/// and `len_range` counts characters, i.e., Unicode scalar values, rather than bytes.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_string;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let s = gen_string(&mut g, &['a', 'é', '€', '.'], '.', 3..=3);
+/// assert_eq!(s.chars().count(), 3);
+/// ```
+pub fn gen_string<E, R>(
+    g: &mut E,
+    alphabet: &[char],
+    stopper: char,
+    len_range: R,
+) -> String
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(!alphabet.is_empty(), "gen_string: alphabet must be non-empty");
+    let (min_len, max_len) = len_bounds(&len_range);
+    assert!(
+        max_len.is_some() || alphabet.contains(&stopper),
+        "gen_string: stopper {:?} is not in alphabet {:?}, and the length is unbounded",
+        stopper,
+        alphabet,
+    );
+    assert!(
+        min_len == 0 || alphabet.iter().any(|x| *x != stopper),
+        "gen_string: alphabet {:?} has no character but stopper {:?}, and the minimum length is {}",
+        alphabet,
+        stopper,
+        min_len,
+    );
+    let chars: Vec<char> = alphabet.iter().copied().filter(|x| *x != stopper).collect();
+    let p = (alphabet.len() - chars.len()) as f64 / alphabet.len() as f64;
+    let len = gen_len(g, p, len_range);
+    (0..len).map(|_| chars[g.choose_index(chars.len())]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    const STOPPER: char = '.';
+    const ALPHABET: &[char] = &['a', 'ß', '\u{20ac}', '\u{1f600}', '.'];
+
+    #[quickcheck]
+    fn both_included(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let s = super::gen_string(&mut g, ALPHABET, STOPPER, a..=b);
+        let n = s.chars().count();
+        assert!(n >= a, "left={}, right={}", n, a);
+        assert!(n <= b, "left={}, right={}", n, b);
+        assert!(s.chars().all(|c| ALPHABET.contains(&c)));
+        assert!(s.chars().all(|c| c != STOPPER));
+    }
+
+    #[test]
+    fn multi_byte() {
+        let mut g = quickcheck::Gen::new(32);
+        let s = super::gen_string(&mut g, &['\u{20ac}', '.'], '.', 4..=4);
+        assert_eq!(s, "\u{20ac}".repeat(4));
+        assert_eq!(s.len(), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in alphabet")]
+    fn no_stopper_unbounded() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_string(&mut g, &['a'], STOPPER, ..);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no character but stopper")]
+    fn only_stoppers() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_string(&mut g, &[STOPPER], STOPPER, 1..);
+    }
+
+    #[test]
+    #[should_panic(expected = "alphabet must be non-empty")]
+    fn empty_alphabet() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_string(&mut g, &[], STOPPER, ..=3);
+    }
+}
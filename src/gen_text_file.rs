@@ -0,0 +1,126 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use quickcheck::Gen;
+
+/// Line-ending styles, used by [gen_text_file].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// `\r`
+    Cr,
+    /// One of the above, randomly chosen per line.
+    Mixed,
+}
+
+impl LineEnding {
+    /// Bytes of the line ending.
+    ///
+    /// It is `None` for [LineEnding::Mixed].
+    pub fn as_bytes(self) -> Option<&'static [u8]> {
+        match self {
+            LineEnding::Lf => Some(b"\n"),
+            LineEnding::CrLf => Some(b"\r\n"),
+            LineEnding::Cr => Some(b"\r"),
+            LineEnding::Mixed => None,
+        }
+    }
+}
+
+/// Generates a text file with lines terminated by `ending`.
+///
+/// Text processors must handle LF, CRLF and CR line endings, often mixed.
+/// Each line is generated by `line_gen` and then terminated by the line ending.
+/// For [LineEnding::Mixed], the line ending is randomly chosen per line.
+///
+/// `line_gen` is expected not to generate `\r` or `\n`;
+/// otherwise, lines cannot be told apart from the output.
+///
+/// The number of lines is exponentially distributed with stop possibility 1/16.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on `line_count_range`.
+pub fn gen_text_file<F, R>(
+    g: &mut Gen,
+    line_gen: F,
+    line_count_range: R,
+    ending: LineEnding,
+) -> Vec<u8>
+where
+    F: Fn(&mut Gen) -> Vec<u8>,
+    R: std::ops::RangeBounds<usize>,
+{
+    const ENDINGS: [LineEnding; 3] = [LineEnding::Lf, LineEnding::CrLf, LineEnding::Cr];
+    let lines = gen_len_by(g, line_count_range, |g| gen_below(g, 16) == 0);
+    let mut res = vec![];
+    for _ in 0..lines {
+        res.extend(line_gen(g));
+        let ending = match ending.as_bytes() {
+            Some(x) => x,
+            None => ENDINGS[gen_below(g, 3) as usize].as_bytes().unwrap(),
+        };
+        res.extend_from_slice(ending);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineEnding;
+    use quickcheck_macros::*;
+
+    // Non-empty lines, so that `\r` followed by `\n` is always CRLF.
+    fn line(g: &mut quickcheck::Gen) -> Vec<u8> {
+        crate::gen_bytes(g, b"abc.", b'.', 1..)
+    }
+
+    fn endings(text: &[u8]) -> Vec<&'static [u8]> {
+        let mut res: Vec<&'static [u8]> = vec![];
+        let mut i = 0;
+        while i < text.len() {
+            if text[i] == b'\n' {
+                res.push(b"\n");
+            } else if text[i] == b'\r' {
+                if text.get(i + 1) == Some(&b'\n') {
+                    res.push(b"\r\n");
+                    i += 1;
+                } else {
+                    res.push(b"\r");
+                }
+            }
+            i += 1;
+        }
+        res
+    }
+
+    #[quickcheck]
+    fn fixed(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        for ending in [LineEnding::Lf, LineEnding::CrLf, LineEnding::Cr] {
+            let text = super::gen_text_file(&mut g, line, a..=b, ending);
+            let xs = endings(&text);
+            assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+            assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+            assert!(xs.iter().all(|x| *x == ending.as_bytes().unwrap()));
+            if !xs.is_empty() {
+                assert!(text.ends_with(ending.as_bytes().unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn mixed() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let text = super::gen_text_file(&mut g, line, 64..=64, LineEnding::Mixed);
+        let xs = endings(&text);
+        assert_eq!(xs.len(), 64);
+        for ending in [LineEnding::Lf, LineEnding::CrLf, LineEnding::Cr] {
+            assert!(xs.contains(&ending.as_bytes().unwrap()), "{:?}", ending);
+        }
+    }
+}
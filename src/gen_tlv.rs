@@ -0,0 +1,106 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::{Entropy, Generated};
+
+/// A type-length-value record generated by [gen_tlv].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlvRecord {
+    pub ty: u8,
+    pub value: Vec<u8>,
+}
+
+/// Generates a stream of type-length-value records.
+///
+/// Each record is encoded as `[type: u8][len: u16 LE][value]`,
+/// where the type is chosen from `types` and the value is generated by
+/// `value_gen` keyed on the type.
+/// Values longer than `u16::MAX` are truncated.
+///
+/// The number of records is exponentially distributed, as if `types` were
+/// followed by one more stopper character.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the range.
+///
+/// It returns both the encoded bytes and the records, as the model, for verification.
+pub fn gen_tlv<E, F, R>(
+    g: &mut E,
+    types: &[u8],
+    value_gen: F,
+    record_count_range: R,
+) -> Generated<Vec<TlvRecord>>
+where
+    E: Entropy + ?Sized,
+    F: Fn(&mut E, u8) -> Vec<u8>,
+    R: std::ops::RangeBounds<usize>,
+{
+    assert!(!types.is_empty(), "gen_tlv: types must be non-empty");
+    let stop_den = types.len() as u64 + 1;
+    let n = gen_len_by(g, record_count_range, |g| gen_below(g, stop_den) == 0);
+    let mut bytes = vec![];
+    let mut records = Vec::with_capacity(n);
+    for _ in 0..n {
+        let ty = types[g.choose_index(types.len())];
+        let mut value = value_gen(g, ty);
+        value.truncate(u16::MAX as usize);
+        bytes.push(ty);
+        bytes.extend((value.len() as u16).to_le_bytes());
+        bytes.extend(&value);
+        records.push(TlvRecord { ty, value });
+    }
+    Generated {
+        bytes,
+        model: records,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TlvRecord;
+    use crate::Generated;
+    use quickcheck_macros::*;
+
+    fn parse(mut xs: &[u8]) -> Vec<TlvRecord> {
+        let mut res = vec![];
+        while !xs.is_empty() {
+            let ty = xs[0];
+            let len = u16::from_le_bytes([xs[1], xs[2]]) as usize;
+            res.push(TlvRecord {
+                ty,
+                value: xs[3..3 + len].to_vec(),
+            });
+            xs = &xs[3 + len..];
+        }
+        res
+    }
+
+    #[quickcheck]
+    fn round_trip(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let Generated { bytes, model: records } = super::gen_tlv(
+            &mut g,
+            &[1, 2],
+            |g, ty| match ty {
+                1 => crate::gen_bytes(g, b"abc.", b'.', ..),
+                _ => crate::gen_bytes(g, b"0123456789.", b'.', 300..),
+            },
+            a..=b,
+        );
+        assert!(records.len() >= a, "left={}, right={}", records.len(), a);
+        assert!(records.len() <= b, "left={}, right={}", records.len(), b);
+        assert_eq!(parse(&bytes), records);
+    }
+
+    #[quickcheck]
+    fn standalone_entropy(seed: u64) {
+        let gen = |e: &mut crate::SeededEntropy| {
+            super::gen_tlv(e, &[1, 2], |e, _| crate::gen_bytes(e, b"abc.", b'.', ..), ..)
+        };
+        let Generated { bytes, model: records } = gen(&mut crate::SeededEntropy::new(seed));
+        assert_eq!(parse(&bytes), records);
+        assert_eq!(gen(&mut crate::SeededEntropy::new(seed)).bytes, bytes);
+    }
+}
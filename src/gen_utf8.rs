@@ -0,0 +1,88 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::{gen_below, gen_between};
+use crate::Entropy;
+
+/// Characters which are rare by chance but troublesome in practice.
+const EDGE_CHARS: &[char] = &[
+    '\u{0}',
+    '\u{7f}',
+    '\u{80}',
+    '\u{7ff}',
+    '\u{800}',
+    // combining grave accent and combining enclosing circle
+    '\u{300}',
+    '\u{20dd}',
+    '\u{d7ff}',
+    '\u{e000}',
+    '\u{feff}',
+    '\u{fffd}',
+    '\u{ffff}',
+    '\u{10000}',
+    // emoji, and a zero width joiner between them
+    '\u{1f600}',
+    '\u{200d}',
+    '\u{1f9d1}',
+    '\u{10ffff}',
+];
+
+/// Generates a string of arbitrary Unicode scalar values.
+///
+/// Characters are drawn across the whole scalar value space, i.e.,
+/// `U+0000..=U+10FFFF` without surrogates, including astral planes.
+/// One in 4 characters is picked from edge cases instead,
+/// e.g., combining marks, emoji, and code points around boundaries of
+/// UTF-8 encoding lengths and of surrogates like `U+D7FF` and `U+E000`.
+///
+/// The length is exponentially distributed with stop possibility 1/16,
+/// and `len_range` counts characters rather than bytes.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_utf8;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let s = gen_utf8(&mut g, 5..=5);
+/// assert_eq!(s.chars().count(), 5);
+/// ```
+pub fn gen_utf8<E, R>(g: &mut E, len_range: R) -> String
+where
+    E: Entropy + ?Sized,
+    R: std::ops::RangeBounds<usize>,
+{
+    let len = gen_len_by(g, len_range, |g| gen_below(g, 16) == 0);
+    (0..len).map(|_| gen_char(g)).collect()
+}
+
+fn gen_char<E: Entropy + ?Sized>(g: &mut E) -> char {
+    if gen_below(g, 4) == 0 {
+        return EDGE_CHARS[g.choose_index(EDGE_CHARS.len())];
+    }
+    const SURROGATES: u64 = 0xe000 - 0xd800;
+    let x = gen_between(g, 0, 0x10ffff - SURROGATES);
+    let x = if x >= 0xd800 { x + SURROGATES } else { x };
+    char::from_u32(x as u32).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn valid_and_counted(n: u8) {
+        let n = usize::from(n);
+        let mut g = quickcheck::Gen::new(32);
+        let s = super::gen_utf8(&mut g, n..=n);
+        assert_eq!(s.chars().count(), n);
+        assert!(std::str::from_utf8(s.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn astral_and_edges() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let s = super::gen_utf8(&mut g, 10000..=10000);
+        assert!(s.chars().any(|c| c > '\u{ffff}'));
+        assert!(s.chars().any(|c| c == '\u{d7ff}'));
+        assert!(s.chars().any(|c| c == '\u{e000}'));
+        assert!(s.chars().any(|c| c == '\u{300}'));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+
+/// Generates a vector of arbitrary elements with exponentially distributed length.
+///
+/// It is [gen_bytes](crate::gen_bytes) for any `T: Arbitrary`.
+/// Each element is generated by `T::arbitrary`.
+/// Unlike most generators here, it takes a [Gen] rather than any
+/// [Entropy](crate::Entropy), because `T::arbitrary` requires a [Gen].
+/// The length is distributed as [gen_bytes](crate::gen_bytes) with stop possibility 1/16.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_vec;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_vec::<u32, _>(&mut g, 0..10);
+/// assert!(xs.len() < 10);
+/// ```
+pub fn gen_vec<T, R>(g: &mut Gen, len_range: R) -> Vec<T>
+where
+    T: Arbitrary,
+    R: std::ops::RangeBounds<usize>,
+{
+    let len = gen_len_by(g, len_range, |g| gen_below(g, 16) == 0);
+    (0..len).map(|_| T::arbitrary(g)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn both_included(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_vec::<(u8, bool), _>(&mut g, a..=b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+    }
+
+    #[quickcheck]
+    fn left_included_right_excluded(a: u8, b: u8) {
+        let (a, b) = match (a, b) {
+            (a, b) if a < b => (a as usize, b as usize),
+            (a, b) if a > b => (b as usize, a as usize),
+            _ => (a as usize, (b as usize) + 1),
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_vec::<String, _>(&mut g, a..b);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() < b, "left={}, right={}", xs.len(), b);
+    }
+
+    #[test]
+    fn mean_length() {
+        const N: usize = 10000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let total: usize = (0..N).map(|_| super::gen_vec::<u8, _>(&mut g, ..).len()).sum();
+        // (1 - p) / p with p = 1/16
+        let mean = total as f64 / N as f64;
+        assert!((mean - 15.0).abs() < 1.0, "mean={}", mean);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::random::gen_below;
+use quickcheck::Gen;
+
+/// Generates data starting with one of several known magic headers.
+///
+/// Format-detection code keys on magic bytes.
+/// This function uniformly chooses a magic from `magics`,
+/// and appends a body generated by `body_gen`.
+/// It returns the bytes and the index of the chosen magic.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, gen_with_magic};
+///
+/// let magics: &[&[u8]] = &[b"\x89PNG", b"GIF8", b"PK\x03\x04"];
+/// let mut g = quickcheck::Gen::new(32);
+/// let (bytes, i) = gen_with_magic(&mut g, magics, |g| gen_bytes(g, b"abc.", b'.', ..));
+/// assert!(bytes.starts_with(magics[i]));
+/// ```
+pub fn gen_with_magic<F>(g: &mut Gen, magics: &[&[u8]], body_gen: F) -> (Vec<u8>, usize)
+where F: Fn(&mut Gen) -> Vec<u8>
+{
+    assert!(!magics.is_empty(), "gen_with_magic: magics must be non-empty");
+    let idx = gen_below(g, magics.len() as u64) as usize;
+    let mut res = magics[idx].to_vec();
+    res.extend(body_gen(g));
+    (res, idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    const MAGICS: &[&[u8]] = &[b"\x89PNG", b"GIF8", b"PK\x03\x04", b""];
+
+    #[quickcheck]
+    fn starts_with_magic(seed: u64) {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+        let (bytes, i) = super::gen_with_magic(
+            &mut g,
+            MAGICS,
+            |g| crate::gen_bytes(g, b"abc.", b'.', ..),
+        );
+        assert!(bytes.starts_with(MAGICS[i]), "{:?}", bytes);
+        assert!(bytes[MAGICS[i].len()..].iter().all(|x| b"abc".contains(x)));
+    }
+
+    #[test]
+    fn covers_magics() {
+        let mut g = quickcheck::Gen::new(32);
+        let idxs: Vec<_> = (0..100)
+            .map(|_| super::gen_with_magic(&mut g, MAGICS, |_| vec![]).1)
+            .collect();
+        for i in 0..MAGICS.len() {
+            assert!(idxs.contains(&i), "{}", i);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::random::gen_between;
+use crate::Generated;
+use quickcheck::Gen;
+
+/// Generates data with an internal pointer.
+///
+/// Formats with internal offset pointers (like ZIP central directories)
+/// have bugs when pointers are valid but unusual.
+/// This function generates a body by `body_gen`,
+/// and prepends a 4-byte header holding a little-endian `u32` offset.
+/// The offset is uniformly chosen, and it is always in bounds:
+/// it is an absolute offset in the output, pointing somewhere in the body,
+/// or exactly to the end of the output.
+/// So `&bytes[offset..]` is always a suffix of the body.
+///
+/// It returns the bytes and the pointer value as the model.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, gen_with_pointer, Generated};
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let Generated { bytes, model: ptr } =
+///     gen_with_pointer(&mut g, |g| gen_bytes(g, b"abc.", b'.', ..));
+/// assert_eq!(bytes[..4], ptr.to_le_bytes());
+/// assert!(ptr as usize >= 4);
+/// assert!(ptr as usize <= bytes.len());
+/// ```
+pub fn gen_with_pointer<F>(g: &mut Gen, body_gen: F) -> Generated<u32>
+where F: Fn(&mut Gen) -> Vec<u8>
+{
+    const HEADER_LEN: usize = 4;
+    let body = body_gen(g);
+    let total = HEADER_LEN + body.len();
+    assert!(total <= u32::MAX as usize, "gen_with_pointer: body is too long");
+    let ptr = gen_between(g, HEADER_LEN as u64, total as u64) as u32;
+    let mut res = Vec::with_capacity(total);
+    res.extend(ptr.to_le_bytes());
+    res.extend(body);
+    Generated {
+        bytes: res,
+        model: ptr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn in_bounds(seed: u64) {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, seed);
+        let crate::Generated { bytes, model: ptr } =
+            super::gen_with_pointer(&mut g, |g| crate::gen_bytes(g, b"abc.", b'.', ..));
+        let embedded = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        assert_eq!(embedded, ptr);
+        let ptr = ptr as usize;
+        assert!(ptr >= 4, "ptr={}", ptr);
+        assert!(ptr <= bytes.len(), "ptr={}, len={}", ptr, bytes.len());
+        assert!(bytes[ptr..].iter().all(|x| b"abc".contains(x)));
+    }
+
+    #[test]
+    fn covers_body() {
+        let mut g = quickcheck::Gen::new(32);
+        let ptrs: Vec<_> = (0..1000)
+            .map(|_| super::gen_with_pointer(&mut g, |_| b"abc".to_vec()).model)
+            .collect();
+        for x in 4..=7 {
+            assert!(ptrs.contains(&x), "{}", x);
+        }
+    }
+}
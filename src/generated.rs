@@ -0,0 +1,26 @@
+/// Bytes generated by a structured generator, together with a model describing them.
+///
+/// Structured generators, e.g., [gen_tlv](crate::gen_tlv) and
+/// [gen_framed](crate::gen_framed), return this type uniformly,
+/// so that properties like `parse(bytes) == model` can be checked directly.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, gen_tlv, Generated};
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let Generated { bytes, model } = gen_tlv(
+///     &mut g,
+///     &[1, 2],
+///     |g, _| gen_bytes(g, b"abc.", b'.', ..),
+///     ..,
+/// );
+/// let total: usize = model.iter().map(|r| 3 + r.value.len()).sum();
+/// assert_eq!(bytes.len(), total);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated<T> {
+    /// The generated bytes.
+    pub bytes: Vec<u8>,
+    /// What `bytes` is expected to be parsed into.
+    pub model: T,
+}
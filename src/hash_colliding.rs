@@ -0,0 +1,127 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::Unshrinkable;
+use quickcheck::{Arbitrary, Gen};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// Keys whose hashes collide in their low bits.
+///
+/// Testing worst cases of hash maps, e.g., for DoS-resistance, needs many keys
+/// colliding in a given hasher `H`.
+/// All keys in a `HashColliding` share the same lowest [COLLIDING_BITS](Self::COLLIDING_BITS)
+/// bits of their hashes, which stresses collision chains.
+///
+/// *   `arbitrary` generates a first key by `T::arbitrary`,
+///     and then searches further keys by brute force.
+///     For each further key, at most [MAX_TRIES](Self::MAX_TRIES) candidates are tried.
+///     Generation stops early when a search fails,
+///     e.g., when the domain of `T` is too small.
+///     The number of keys is exponentially distributed.
+/// *   `shrink` only removes keys, so the remaining ones still collide.
+///
+/// Hashers are created by `H::default()`, so `H` must be deterministic,
+/// e.g., `std::collections::hash_map::DefaultHasher`.
+pub struct HashColliding<T, H>(pub Vec<T>, PhantomData<fn() -> H>);
+
+impl<T, H> HashColliding<T, H>
+where
+    T: Hash,
+    H: Hasher + Default,
+{
+    /// The number of low bits that hashes of keys share.
+    pub const COLLIDING_BITS: u32 = 8;
+    /// The maximum number of candidates tried for each key.
+    pub const MAX_TRIES: usize = 1 << 16;
+
+    pub fn new(keys: Vec<T>) -> Self {
+        HashColliding(keys, PhantomData)
+    }
+
+    /// Hashes a key by `H` and returns the bits that keys share.
+    pub fn low_bits(key: &T) -> u64 {
+        let mut h = H::default();
+        key.hash(&mut h);
+        h.finish() & ((1 << Self::COLLIDING_BITS) - 1)
+    }
+}
+
+impl<T: Clone, H> Clone for HashColliding<T, H> {
+    fn clone(&self) -> Self {
+        HashColliding(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: Debug, H> Debug for HashColliding<T, H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("HashColliding").field(&self.0).finish()
+    }
+}
+
+impl<T, H> Arbitrary for HashColliding<T, H>
+where
+    T: Arbitrary + Debug + Hash,
+    H: Hasher + Default + 'static,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n = gen_len_by(g, .., |g| gen_below(g, 8) == 0);
+        let mut res: Vec<T> = Vec::with_capacity(n);
+        if n == 0 {
+            return Self::new(res);
+        }
+        let first = T::arbitrary(g);
+        let target = Self::low_bits(&first);
+        res.push(first);
+        'outer: while res.len() < n {
+            for _ in 0..Self::MAX_TRIES {
+                let x = T::arbitrary(g);
+                if Self::low_bits(&x) == target {
+                    res.push(x);
+                    continue 'outer;
+                }
+            }
+            break;
+        }
+        Self::new(res)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let xs: Vec<_> = self.0.iter().cloned().map(Unshrinkable::new).collect();
+        Box::new(xs.shrink().map(|xs| {
+            Self::new(xs.into_iter().map(|x| x.take()).collect())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    type Keys = super::HashColliding<u64, DefaultHasher>;
+
+    fn check(keys: &Keys) {
+        if let Some(first) = keys.0.first() {
+            let target = Keys::low_bits(first);
+            assert!(keys.0.iter().all(|x| Keys::low_bits(x) == target), "{:?}", keys);
+        }
+    }
+
+    #[quickcheck]
+    fn colliding(trial: Keys) {
+        check(&trial);
+        for x in trial.shrink() {
+            assert!(x.0.len() < trial.0.len());
+            check(&x);
+        }
+    }
+
+    #[test]
+    fn many_keys() {
+        let mut g = quickcheck::Gen::new(32);
+        let n: usize = (0..100).map(|_| Keys::arbitrary(&mut g).0.len()).sum();
+        assert!(n > 100);
+    }
+}
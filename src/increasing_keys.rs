@@ -0,0 +1,108 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::{gen_below, gen_between};
+use quickcheck::{Arbitrary, Gen};
+
+/// Strictly increasing keys with controlled gaps.
+///
+/// It is useful to test B-tree insertion with increasing keys.
+///
+/// *   `arbitrary` starts at a small base not larger than `MAX_GAP`,
+///     and then adds a random gap between `MIN_GAP` and `MAX_GAP` (both inclusive)
+///     per element.
+///     The number of keys is exponentially distributed, just like [gen_bytes](crate::gen_bytes).
+///     Generation stops early if the next key overflows `u64`.
+/// *   `shrink` removes keys from both ends and rebases keys towards 0.
+///     Therefore, every shrinked value is still strictly increasing and
+///     its gaps are still between `MIN_GAP` and `MAX_GAP`.
+///
+/// `MIN_GAP` must be positive and not larger than `MAX_GAP`.
+///
+/// ```rust
+/// use rs_quickcheck_util::IncreasingKeys;
+/// use quickcheck::{Arbitrary, Gen};
+///
+/// let mut g = Gen::new(32);
+/// let IncreasingKeys(keys) = IncreasingKeys::<1, 10>::arbitrary(&mut g);
+/// assert!(keys.windows(2).all(|w| w[0] < w[1]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncreasingKeys<const MIN_GAP: u64, const MAX_GAP: u64>(pub Vec<u64>);
+
+impl<const MIN_GAP: u64, const MAX_GAP: u64> Arbitrary for IncreasingKeys<MIN_GAP, MAX_GAP> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        assert!(MIN_GAP > 0, "IncreasingKeys: MIN_GAP must be positive");
+        assert!(
+            MIN_GAP <= MAX_GAP,
+            "IncreasingKeys: MIN_GAP={} > MAX_GAP={}",
+            MIN_GAP,
+            MAX_GAP,
+        );
+        let n = gen_len_by(g, .., |g| gen_below(g, 4) == 0);
+        let mut res = Vec::with_capacity(n);
+        let mut next = Some(gen_between(g, 0, MAX_GAP));
+        while res.len() < n {
+            let x = match next {
+                Some(x) => x,
+                None => break,
+            };
+            res.push(x);
+            next = x.checked_add(gen_between(g, MIN_GAP, MAX_GAP));
+        }
+        IncreasingKeys(res)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let xs = self.0.clone();
+        let n = xs.len();
+        let mut res = vec![];
+        if let Some(&base) = xs.first() {
+            if base > 0 {
+                res.push(IncreasingKeys(xs.iter().map(|x| x - base).collect()));
+            }
+        }
+        let mut removed = n;
+        while removed > 0 {
+            res.push(IncreasingKeys(xs[..n - removed].to_vec()));
+            if removed < n {
+                res.push(IncreasingKeys(xs[removed..].to_vec()));
+            }
+            removed /= 2;
+        }
+        Box::new(res.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    const MIN_GAP: u64 = 3;
+    const MAX_GAP: u64 = 7;
+    type Keys = super::IncreasingKeys<MIN_GAP, MAX_GAP>;
+
+    fn check(xs: &[u64]) {
+        for w in xs.windows(2) {
+            assert!(w[0] < w[1], "{:?}", xs);
+            let gap = w[1] - w[0];
+            assert!(gap >= MIN_GAP, "{:?}", xs);
+            assert!(gap <= MAX_GAP, "{:?}", xs);
+        }
+    }
+
+    #[quickcheck]
+    fn arbitrary_and_shrink(trial: Keys) {
+        check(&trial.0);
+        for x in trial.shrink() {
+            check(&x.0);
+            assert!(x.0.len() <= trial.0.len());
+        }
+    }
+
+    #[test]
+    fn shrink_towards_empty() {
+        let xs = super::IncreasingKeys::<MIN_GAP, MAX_GAP>(vec![5, 8, 15, 18]);
+        assert!(xs.shrink().any(|x| x.0.is_empty()));
+        assert!(xs.shrink().any(|x| x.0 == vec![0, 3, 10, 13]));
+    }
+}
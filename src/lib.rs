@@ -1,9 +1,159 @@
 #![doc = include_str!("../README.md")]
 
+// so that derived code refers to this crate by name in its own tests
+#[cfg(test)]
+extern crate self as rs_quickcheck_util;
+
+#[cfg(feature = "derive")]
+pub use rs_quickcheck_util_derive::ShrinkFields;
+
+mod all_variants;
+pub use self::all_variants::*;
+mod canonical;
+pub use self::canonical::*;
+mod chain_shrinkers;
+pub use self::chain_shrinkers::*;
+mod choose_weighted;
+pub use self::choose_weighted::*;
+mod combinators;
+pub use self::combinators::*;
+mod dense_map;
+pub use self::dense_map::*;
+mod disjoint_intervals;
+pub use self::disjoint_intervals::*;
+mod distinct_gen;
+pub use self::distinct_gen::*;
+mod edit_n;
+pub use self::edit_n::*;
+mod entropy;
+pub use self::entropy::*;
+mod flags;
+pub use self::flags::*;
+mod framed;
+pub use self::framed::*;
+mod gen_ascii_identifier;
+pub use self::gen_ascii_identifier::*;
+mod gen_bad_length_prefixed;
+pub use self::gen_bad_length_prefixed::*;
+mod gen_bool;
+pub use self::gen_bool::*;
+mod gen_btree_map;
+pub use self::gen_btree_map::*;
+mod gen_burst_lengths;
+pub use self::gen_burst_lengths::*;
 mod gen_bytes;
 pub use self::gen_bytes::*;
+mod gen_bytes_avoiding;
+pub use self::gen_bytes_avoiding::*;
+mod gen_bytes_empty_biased;
+pub use self::gen_bytes_empty_biased::*;
+mod gen_bytes_profiled;
+pub use self::gen_bytes_profiled::*;
+mod gen_bytes_stateful;
+pub use self::gen_bytes_stateful::*;
+mod gen_bytes_straddling;
+pub use self::gen_bytes_straddling::*;
+mod gen_bytes_uniform_len;
+pub use self::gen_bytes_uniform_len::*;
+mod gen_bytes_value;
+pub use self::gen_bytes_value::*;
+mod gen_bytes_weighted;
+pub use self::gen_bytes_weighted::*;
+mod gen_bytes_with_dupes;
+pub use self::gen_bytes_with_dupes::*;
+mod gen_ext;
+pub use self::gen_ext::*;
+mod gen_f64_in_range;
+pub use self::gen_f64_in_range::*;
+mod gen_hex;
+pub use self::gen_hex::*;
+mod gen_int_in_range;
+pub use self::gen_int_in_range::*;
+mod gen_invalid_utf8;
+pub use self::gen_invalid_utf8::*;
+mod gen_kv_pairs;
+pub use self::gen_kv_pairs::*;
+#[cfg(feature = "serde_json")]
+mod gen_lenient_json;
+#[cfg(feature = "serde_json")]
+pub use self::gen_lenient_json::*;
+mod gen_misaligned;
+pub use self::gen_misaligned::*;
+mod gen_mixed_case;
+pub use self::gen_mixed_case::*;
+mod gen_palindrome;
+pub use self::gen_palindrome::*;
+mod gen_partition;
+pub use self::gen_partition::*;
+mod gen_recoverable;
+pub use self::gen_recoverable::*;
+mod gen_recursive;
+pub use self::gen_recursive::*;
+mod gen_string;
+pub use self::gen_string::*;
+mod gen_text_file;
+pub use self::gen_text_file::*;
+mod gen_tlv;
+pub use self::gen_tlv::*;
+mod gen_utf8;
+pub use self::gen_utf8::*;
+mod gen_vec;
+pub use self::gen_vec::*;
+mod gen_with_magic;
+pub use self::gen_with_magic::*;
+mod gen_with_pointer;
+pub use self::gen_with_pointer::*;
+mod generated;
+pub use self::generated::*;
+mod hash_colliding;
+pub use self::hash_colliding::*;
+mod increasing_keys;
+pub use self::increasing_keys::*;
+mod minimal_failing_by;
+pub use self::minimal_failing_by::*;
+mod near_duplicate;
+pub use self::near_duplicate::*;
+mod nonempty;
+pub use self::nonempty::*;
+mod permutation;
+pub use self::permutation::*;
+mod permutation_histogram;
+pub use self::permutation_histogram::*;
+mod random;
+mod record_builder;
+pub use self::record_builder::*;
+mod ref_graph;
+pub use self::ref_graph::*;
+mod sawtooth;
+pub use self::sawtooth::*;
+mod seeded_gen;
+pub use self::seeded_gen::*;
 mod shrink_field;
+mod shrink_filter;
+pub use self::shrink_filter::*;
+mod shrink_product;
+pub use self::shrink_product::*;
+mod shrink_towards;
+pub use self::shrink_towards::*;
+mod shrink_trace;
+pub use self::shrink_trace::*;
 mod shuffle;
 pub use self::shuffle::*;
+mod slice_gen_ext;
+pub use self::slice_gen_ext::*;
+mod sorted_vec;
+pub use self::sorted_vec::*;
+mod state_machine;
+pub use self::state_machine::*;
+mod subsequence;
+pub use self::subsequence::*;
+mod truncate;
+pub use self::truncate::*;
+mod unique_vec;
+pub use self::unique_vec::*;
 mod unshrinkable;
 pub use self::unshrinkable::*;
+mod weighted_enum;
+pub use self::weighted_enum::*;
+mod whitespace_heavy;
+pub use self::whitespace_heavy::*;
@@ -0,0 +1,111 @@
+use quickcheck::Arbitrary;
+
+/// Shrinks a failing value towards the minimum of a user-supplied cost.
+///
+/// quickcheck shrinks towards structurally smaller values,
+/// but sometimes a domain cost (e.g., the number of distinct keys) matters more.
+/// Starting from `start`, on which `prop` fails (i.e., returns `false`),
+/// this function examines all shrinked candidates,
+/// and always steps to the failing one with the lowest `cost`,
+/// as long as its cost is lower than the current one.
+/// It stops when there is no such candidate,
+/// so it terminates even if shrinking yields equal-cost candidates back and forth.
+///
+/// If `prop` holds on `start`, `start` itself is returned.
+///
+/// ```rust
+/// use rs_quickcheck_util::minimal_failing_by;
+///
+/// let start: Vec<u32> = vec![5, 500, 7];
+/// // fails on vectors of at least 3 elements or with an element of at least 500
+/// let prop = |xs: &Vec<u32>| xs.len() < 3 && xs.iter().all(|x| *x < 500);
+/// let min = minimal_failing_by(start, prop, |xs| xs.iter().map(|x| *x as u64).sum());
+/// assert_eq!(min, vec![0, 0, 0]);
+/// ```
+pub fn minimal_failing_by<T, P, C>(start: T, prop: P, cost: C) -> T
+where
+    T: Arbitrary + Clone,
+    P: Fn(&T) -> bool,
+    C: Fn(&T) -> u64,
+{
+    if prop(&start) {
+        return start;
+    }
+    let mut cur = start;
+    let mut cur_cost = cost(&cur);
+    loop {
+        let best = cur.shrink()
+            .filter(|x| !prop(x))
+            .map(|x| (cost(&x), x))
+            .min_by_key(|(c, _)| *c);
+        match best {
+            Some((c, x)) if c < cur_cost => {
+                cur = x;
+                cur_cost = c;
+            }
+            _ => {
+                break;
+            }
+        }
+    }
+    cur
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+
+    fn prop(xs: &[u32]) -> bool {
+        xs.len() < 3 && xs.iter().all(|x| *x < 500)
+    }
+
+    fn cost(xs: &[u32]) -> u64 {
+        xs.iter().map(|x| u64::from(*x)).sum()
+    }
+
+    /// Greedy shrinking, as quickcheck does, i.e., following the first failing candidate.
+    fn structural_minimum(start: Vec<u32>) -> Vec<u32> {
+        let mut cur = start;
+        while let Some(x) = cur.shrink().find(|x| !prop(x)) {
+            cur = x;
+        }
+        cur
+    }
+
+    #[test]
+    fn differs_from_structural_minimum() {
+        let start = vec![5, 500, 7];
+        let structural = structural_minimum(start.clone());
+        let by_cost = super::minimal_failing_by(start, |xs| prop(xs), |xs| cost(xs));
+        assert!(!prop(&structural));
+        assert!(!prop(&by_cost));
+        assert_eq!(structural, vec![500]);
+        assert_eq!(by_cost, vec![0, 0, 0]);
+        assert!(cost(&by_cost) < cost(&structural));
+    }
+
+    #[test]
+    fn passing_start() {
+        let start = vec![1, 2];
+        assert_eq!(super::minimal_failing_by(start.clone(), |xs| prop(xs), |xs| cost(xs)), start);
+    }
+
+    /// Shrinks to the other state forever.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Flip(bool);
+
+    impl Arbitrary for Flip {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            Flip(bool::arbitrary(g))
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new(std::iter::once(Flip(!self.0)))
+        }
+    }
+
+    #[test]
+    fn equal_cost_cycle() {
+        assert_eq!(super::minimal_failing_by(Flip(true), |_| false, |_| 0), Flip(true));
+    }
+}
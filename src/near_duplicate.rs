@@ -0,0 +1,132 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::{gen_below, gen_between};
+use quickcheck::{Arbitrary, Gen};
+
+/// A pair of almost-equal byte sequences.
+///
+/// Fuzzing similarity/fuzzy-match code needs pairs of almost-equal inputs.
+///
+/// *   `arbitrary` generates a base sequence, and then a variant by applying
+///     at most `MAX_EDITS` random edits, i.e., insertions, deletions and substitutions,
+///     to a copy of the base.
+///     So the edit distance between them is at most `MAX_EDITS`.
+///     The length of the base is exponentially distributed.
+/// *   `shrink` removes common bytes, i.e., from their common prefix or common suffix,
+///     from both members, or replaces the longer member by the shorter one.
+///     So the edit distance never grows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearDuplicate<const MAX_EDITS: usize>(pub Vec<u8>, pub Vec<u8>);
+
+impl<const MAX_EDITS: usize> Arbitrary for NearDuplicate<MAX_EDITS> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n = gen_len_by(g, .., |g| gen_below(g, 8) == 0);
+        let base: Vec<u8> = (0..n).map(|_| u8::arbitrary(g)).collect();
+        let mut variant = base.clone();
+        let edits = gen_between(g, 0, MAX_EDITS as u64);
+        for _ in 0..edits {
+            let len = variant.len() as u64;
+            match gen_below(g, 3) {
+                0 => {
+                    let i = gen_below(g, len + 1) as usize;
+                    variant.insert(i, u8::arbitrary(g));
+                }
+                1 if len > 0 => {
+                    let i = gen_below(g, len) as usize;
+                    variant.remove(i);
+                }
+                2 if len > 0 => {
+                    let i = gen_below(g, len) as usize;
+                    variant[i] = u8::arbitrary(g);
+                }
+                _ => {}
+            }
+        }
+        NearDuplicate(base, variant)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let xs = &self.0;
+        let ys = &self.1;
+        let min_len = xs.len().min(ys.len());
+        let prefix = xs.iter().zip(ys.iter()).take_while(|(x, y)| x == y).count();
+        let suffix = xs.iter().rev().zip(ys.iter().rev())
+            .take(min_len - prefix)
+            .take_while(|(x, y)| x == y)
+            .count();
+        let mut res = vec![];
+        if xs != ys {
+            if xs.len() <= ys.len() {
+                res.push(NearDuplicate(xs.clone(), xs.clone()));
+            }
+            if ys.len() <= xs.len() {
+                res.push(NearDuplicate(ys.clone(), ys.clone()));
+            }
+        }
+        if prefix > 0 {
+            res.push(NearDuplicate(xs[prefix..].to_vec(), ys[prefix..].to_vec()));
+        }
+        if suffix > 0 {
+            res.push(NearDuplicate(
+                xs[..xs.len() - suffix].to_vec(),
+                ys[..ys.len() - suffix].to_vec(),
+            ));
+        }
+        for i in 0..prefix {
+            let mut xs = xs.clone();
+            let mut ys = ys.clone();
+            xs.remove(i);
+            ys.remove(i);
+            res.push(NearDuplicate(xs, ys));
+        }
+        for i in 1..=suffix {
+            let mut xs = xs.clone();
+            let mut ys = ys.clone();
+            xs.remove(xs.len() - i);
+            ys.remove(ys.len() - i);
+            res.push(NearDuplicate(xs, ys));
+        }
+        Box::new(res.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    const MAX_EDITS: usize = 3;
+
+    fn edit_distance(xs: &[u8], ys: &[u8]) -> usize {
+        let mut prev: Vec<usize> = (0..=ys.len()).collect();
+        for (i, x) in xs.iter().enumerate() {
+            let mut cur = vec![i + 1; ys.len() + 1];
+            for (j, y) in ys.iter().enumerate() {
+                let sub = prev[j] + if x == y {0} else {1};
+                cur[j + 1] = sub.min(prev[j + 1] + 1).min(cur[j] + 1);
+            }
+            prev = cur;
+        }
+        prev[ys.len()]
+    }
+
+    #[quickcheck]
+    fn within_edit_distance(trial: super::NearDuplicate<MAX_EDITS>) {
+        let d = edit_distance(&trial.0, &trial.1);
+        assert!(d <= MAX_EDITS, "{:?}", trial);
+        for x in trial.shrink() {
+            let dx = edit_distance(&x.0, &x.1);
+            assert!(dx <= d, "{:?} -> {:?}", trial, x);
+            assert!(x.0.len() + x.1.len() <= trial.0.len() + trial.1.len());
+        }
+    }
+
+    #[test]
+    fn differ_sometimes() {
+        let mut g = quickcheck::Gen::new(32);
+        let n = (0..100)
+            .map(|_| super::NearDuplicate::<MAX_EDITS>::arbitrary(&mut g))
+            .filter(|x| x.0 != x.1)
+            .count();
+        assert!(n > 0);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::gen_bytes::{gen_len_by, len_bounds};
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+use std::ops::{Bound, Deref};
+
+/// A string which is never empty.
+///
+/// It covers the extremely common "assume non-empty" precondition
+/// without per-property discards.
+///
+/// *   `arbitrary` generates at least one character.
+///     The length is exponentially distributed.
+/// *   `shrink` never yields the empty string.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmptyString(pub String);
+
+impl Deref for NonEmptyString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Arbitrary for NonEmptyString {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n = gen_len_by(g, 1.., |g| gen_below(g, 4) == 0);
+        NonEmptyString((0..n).map(|_| char::arbitrary(g)).collect())
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink()
+            .filter(|x| !x.is_empty())
+            .map(NonEmptyString))
+    }
+}
+
+/// A byte sequence which is never empty.
+///
+/// *   `arbitrary` generates at least one byte.
+///     The length is exponentially distributed.
+/// *   `shrink` never yields the empty sequence.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmptyBytes(pub Vec<u8>);
+
+impl Deref for NonEmptyBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Arbitrary for NonEmptyBytes {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n = gen_len_by(g, 1.., |g| gen_below(g, 4) == 0);
+        NonEmptyBytes((0..n).map(|_| u8::arbitrary(g)).collect())
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink()
+            .filter(|x| !x.is_empty())
+            .map(NonEmptyBytes))
+    }
+}
+
+/// Generates a non-empty vector of arbitrary elements.
+///
+/// It is [gen_vec](crate::gen_vec), except that the lower bound of `len_range`
+/// is raised to 1 if it is smaller.
+/// It panics if `len_range` allows no non-empty length.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_nonempty_vec;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_nonempty_vec::<u32, _>(&mut g, ..);
+/// assert!(!xs.is_empty());
+/// ```
+pub fn gen_nonempty_vec<T, R>(g: &mut Gen, len_range: R) -> Vec<T>
+where
+    T: Arbitrary,
+    R: std::ops::RangeBounds<usize>,
+{
+    let (min_len, max_len) = len_bounds(&len_range);
+    let min_len = min_len.max(1);
+    assert!(
+        max_len.map_or(true, |n| n > min_len),
+        "gen_nonempty_vec: {:?} allows no non-empty length",
+        (len_range.start_bound(), len_range.end_bound()),
+    );
+    let max_len = max_len.map_or(Bound::Unbounded, Bound::Excluded);
+    crate::gen_vec(g, (Bound::Included(min_len), max_len))
+}
+
+/// A vector which is never empty.
+///
+/// *   `arbitrary` generates by [gen_nonempty_vec].
+/// *   `shrink` never yields the empty vector.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmptyVec<T>(pub Vec<T>);
+
+impl<T> Deref for NonEmptyVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for NonEmptyVec<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        NonEmptyVec(gen_nonempty_vec(g, ..))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink()
+            .filter(|x| !x.is_empty())
+            .map(NonEmptyVec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn nonempty_string(trial: super::NonEmptyString) {
+        assert!(!trial.is_empty());
+        for x in trial.shrink() {
+            assert!(!x.is_empty());
+        }
+    }
+
+    #[quickcheck]
+    fn nonempty_bytes(trial: super::NonEmptyBytes) {
+        assert!(!trial.is_empty());
+        for x in trial.shrink() {
+            assert!(!x.is_empty());
+        }
+    }
+
+    #[quickcheck]
+    fn nonempty_vec(trial: super::NonEmptyVec<u8>) {
+        assert!(!trial.is_empty());
+        for x in trial.shrink() {
+            assert!(!x.is_empty());
+        }
+    }
+
+    #[quickcheck]
+    fn gen_nonempty_vec(b: u8) {
+        let b = usize::from(b);
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_nonempty_vec::<u32, _>(&mut g, ..=b.max(1));
+        assert!(!xs.is_empty());
+        assert!(xs.len() <= b.max(1), "left={}, right={}", xs.len(), b);
+    }
+
+    #[test]
+    #[should_panic(expected = "allows no non-empty length")]
+    fn no_nonempty_length() {
+        let mut g = quickcheck::Gen::new(32);
+        super::gen_nonempty_vec::<u32, _>(&mut g, ..1);
+    }
+}
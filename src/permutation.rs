@@ -0,0 +1,92 @@
+use crate::Entropy;
+
+/// Generates a uniformly random permutation of `0..n`.
+///
+/// It shares the unbiased Fisher–Yates core with [shuffle](crate::shuffle).
+/// With the raw index permutation, the same reordering can be applied to
+/// several collections, e.g., by [apply_permutation].
+pub fn gen_permutation<E>(g: &mut E, n: usize) -> Vec<usize>
+where E: Entropy + ?Sized
+{
+    let mut res: Vec<usize> = (0..n).collect();
+    crate::shuffle(g, &mut res);
+    res
+}
+
+/// Reorders a slice by a permutation.
+///
+/// The mapping is `output[i] = xs[perm[i]]`.
+/// It panics unless `perm` is a permutation of `0..xs.len()`.
+///
+/// ```rust
+/// use rs_quickcheck_util::{apply_permutation, gen_permutation};
+///
+/// let keys = ["a", "b", "c"];
+/// let values = [1, 2, 3];
+/// let mut g = quickcheck::Gen::new(32);
+/// let perm = gen_permutation(&mut g, keys.len());
+/// let keys = apply_permutation(&perm, &keys);
+/// let values = apply_permutation(&perm, &values);
+/// // rows stay aligned
+/// for (k, v) in keys.iter().zip(values.iter()) {
+///     assert_eq!(["a", "b", "c"][*v - 1], *k);
+/// }
+/// ```
+pub fn apply_permutation<T: Clone>(perm: &[usize], xs: &[T]) -> Vec<T> {
+    assert!(
+        perm.len() == xs.len() && is_permutation(perm),
+        "apply_permutation: {:?} is not a permutation of 0..{}",
+        perm,
+        xs.len(),
+    );
+    perm.iter().map(|i| xs[*i].clone()).collect()
+}
+
+fn is_permutation(perm: &[usize]) -> bool {
+    let mut seen = vec![false; perm.len()];
+    for x in perm.iter() {
+        if *x >= perm.len() || seen[*x] {
+            return false;
+        }
+        seen[*x] = true;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_permutation;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn valid_permutation(n: u8) {
+        let mut g = quickcheck::Gen::new(32);
+        let perm = super::gen_permutation(&mut g, usize::from(n));
+        assert_eq!(perm.len(), usize::from(n));
+        assert!(is_permutation(&perm), "{:?}", perm);
+    }
+
+    #[test]
+    fn empty() {
+        let mut g = quickcheck::Gen::new(32);
+        assert!(super::gen_permutation(&mut g, 0).is_empty());
+    }
+
+    #[quickcheck]
+    fn inverse_is_identity(xs: Vec<u32>) {
+        let mut g = quickcheck::Gen::new(32);
+        let perm = super::gen_permutation(&mut g, xs.len());
+        let mut inverse = vec![0; perm.len()];
+        for (i, x) in perm.iter().enumerate() {
+            inverse[*x] = i;
+        }
+        let ys = super::apply_permutation(&perm, &xs);
+        assert_eq!(super::apply_permutation(&inverse, &ys), xs);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a permutation")]
+    fn invalid_permutation() {
+        super::apply_permutation(&[0, 0, 1], &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Counts occurrences of each permutation among samples.
+pub fn permutation_histogram<T>(samples: &[Vec<T>]) -> HashMap<Vec<T>, usize>
+where T: Ord + Hash + Clone
+{
+    let mut res = HashMap::new();
+    for x in samples.iter() {
+        *res.entry(x.clone()).or_insert(0) += 1;
+    }
+    res
+}
+
+/// Asserts that samples are near-uniformly distributed over permutations.
+///
+/// It is useful to regression-test shuffle-based generators, e.g., to catch modulo bias.
+/// It panics unless
+///
+/// *   all samples are permutations of the same multiset,
+/// *   every distinct permutation of the multiset appears, and
+/// *   each permutation appears $c$ times where $|c-e| \leq tolerance \cdot e$,
+///     and $e$ is the expected number of occurrences.
+///
+/// There should be enough samples, so that $e$ is reasonably large.
+///
+/// ```rust
+/// use rs_quickcheck_util::assert_uniform_permutations;
+///
+/// let samples = vec![vec![1, 2], vec![2, 1], vec![2, 1], vec![1, 2]];
+/// assert_uniform_permutations(&samples, 0.1);
+/// ```
+pub fn assert_uniform_permutations<T>(samples: &[Vec<T>], tolerance: f64)
+where T: Ord + Hash + Clone + Debug
+{
+    assert!(!samples.is_empty(), "assert_uniform_permutations: no samples");
+    let mut multiset = samples[0].clone();
+    multiset.sort();
+    for x in samples.iter() {
+        let mut y = x.clone();
+        y.sort();
+        assert_eq!(y, multiset, "{:?} is not a permutation of {:?}", x, samples[0]);
+    }
+    let permutations = count_permutations(&multiset);
+    let hist = permutation_histogram(samples);
+    assert_eq!(
+        hist.len() as f64,
+        permutations,
+        "only {} of {} permutations appear",
+        hist.len(),
+        permutations,
+    );
+    let expected = samples.len() as f64 / permutations;
+    for (x, c) in hist.iter() {
+        let diff = (*c as f64 - expected).abs();
+        assert!(
+            diff <= tolerance * expected,
+            "{:?} appears {} times, but {} times are expected",
+            x,
+            c,
+            expected,
+        );
+    }
+}
+
+/// The number of distinct permutations of a sorted multiset.
+fn count_permutations<T: Eq>(sorted: &[T]) -> f64 {
+    let mut res = 1.0;
+    let mut run = 0;
+    for (i, x) in sorted.iter().enumerate() {
+        if i > 0 && sorted[i - 1] == *x {
+            run += 1;
+        } else {
+            run = 1;
+        }
+        // n!/(m1! m2! ...) = prod (i+1)/run over elements
+        res *= (i + 1) as f64 / run as f64;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn uniform() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let samples: Vec<Vec<u8>> = (0..24000)
+            .map(|_| {
+                let mut xs = vec![0, 1, 2, 3];
+                crate::shuffle(&mut g, &mut xs);
+                xs
+            })
+            .collect();
+        let hist = super::permutation_histogram(&samples);
+        assert_eq!(hist.len(), 24);
+        assert_eq!(hist.values().sum::<usize>(), samples.len());
+        super::assert_uniform_permutations(&samples, 0.15);
+    }
+
+    #[test]
+    fn uniform_with_duplicates() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let samples: Vec<Vec<u8>> = (0..12000)
+            .map(|_| {
+                let mut xs = vec![0, 0, 1, 2];
+                crate::shuffle(&mut g, &mut xs);
+                xs
+            })
+            .collect();
+        assert_eq!(super::permutation_histogram(&samples).len(), 12);
+        super::assert_uniform_permutations(&samples, 0.15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn biased() {
+        // [0, 1, 2] appears twice as often as others.
+        let perms = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+        let samples: Vec<Vec<u8>> = (0..1000)
+            .flat_map(|_| {
+                perms.iter()
+                    .map(|x| x.to_vec())
+                    .chain(std::iter::once(vec![0, 1, 2]))
+            })
+            .collect();
+        super::assert_uniform_permutations(&samples, 0.15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing() {
+        let samples = vec![vec![0, 1, 2]; 100];
+        super::assert_uniform_permutations(&samples, 0.15);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::Entropy;
+
+/// Generates a uniformly distributed `u64`.
+pub(crate) fn gen_u64<E: Entropy + ?Sized>(g: &mut E) -> u64 {
+    g.next_u64()
+}
+
+/// Generates a uniformly distributed number in $[0, n)$.
+///
+/// Rejection sampling is applied, so there is no modulo bias.
+/// `n` must be positive.
+pub(crate) fn gen_below<E: Entropy + ?Sized>(g: &mut E, n: u64) -> u64 {
+    assert!(n > 0, "gen_below: n must be positive");
+    // 2^64 mod n, i.e., the number of values to reject.
+    let rem = (u64::MAX % n + 1) % n;
+    loop {
+        let x = gen_u64(g);
+        if x <= u64::MAX - rem {
+            return x % n;
+        }
+    }
+}
+
+/// Generates a uniformly distributed number in $[0, n)$, where `n` can exceed `u64`.
+///
+/// `n` must be positive.
+pub(crate) fn gen_below_u128<E: Entropy + ?Sized>(g: &mut E, n: u128) -> u128 {
+    assert!(n > 0, "gen_below_u128: n must be positive");
+    if let Ok(n) = u64::try_from(n) {
+        return u128::from(gen_below(g, n));
+    }
+    let rem = (u128::MAX % n + 1) % n;
+    loop {
+        let x = (u128::from(gen_u64(g)) << 64) | u128::from(gen_u64(g));
+        if x <= u128::MAX - rem {
+            return x % n;
+        }
+    }
+}
+
+/// Generates a uniformly distributed number in $[lo, hi]$.
+pub(crate) fn gen_between<E: Entropy + ?Sized>(g: &mut E, lo: u64, hi: u64) -> u64 {
+    assert!(lo <= hi, "gen_between: lo={} > hi={}", lo, hi);
+    match (hi - lo).checked_add(1) {
+        Some(n) => lo + gen_below(g, n),
+        None => gen_u64(g),
+    }
+}
+
+/// Generates a number uniformly distributed in $[0, 1)$, from 53 random bits,
+/// i.e., the precision of `f64`.
+pub(crate) fn gen_unit_f64<E: Entropy + ?Sized>(g: &mut E) -> f64 {
+    (gen_u64(g) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Returns `true` with possibility $num/den$.
+pub(crate) fn gen_ratio<E: Entropy + ?Sized>(g: &mut E, num: u32, den: u32) -> bool {
+    assert!(den > 0, "gen_ratio: denominator must be positive");
+    assert!(num <= den, "gen_ratio: num={} > den={}", num, den);
+    gen_below(g, u64::from(den)) < u64::from(num)
+}
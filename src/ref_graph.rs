@@ -0,0 +1,116 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+
+/// Directed graphs by index, deliberately allowing back-references and cycles.
+///
+/// It is useful to test object-graph serializers.
+/// The `i`-th element is the adjacency list of node `i`,
+/// and every reference is an index of a node.
+///
+/// *   `arbitrary` generates nodes whose references are uniformly chosen from all nodes,
+///     including the node itself.
+///     Both the number of nodes and the lengths of adjacency lists are
+///     exponentially distributed.
+/// *   `shrink` removes nodes, from the last one,
+///     dropping references to the removed node and renumbering the rest.
+///     Then it removes references one by one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefGraph(pub Vec<Vec<usize>>);
+
+impl RefGraph {
+    fn remove_node(&self, idx: usize) -> Self {
+        let nodes = self.0.iter()
+            .enumerate()
+            .filter(|(i, _)| *i != idx)
+            .map(|(_, refs)| {
+                refs.iter()
+                    .filter(|x| **x != idx)
+                    .map(|x| if *x > idx { *x - 1 } else { *x })
+                    .collect()
+            })
+            .collect();
+        RefGraph(nodes)
+    }
+
+    fn remove_ref(&self, node: usize, idx: usize) -> Self {
+        let mut res = self.clone();
+        res.0[node].remove(idx);
+        res
+    }
+}
+
+impl Arbitrary for RefGraph {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n = gen_len_by(g, .., |g| gen_below(g, 8) == 0);
+        let nodes = (0..n)
+            .map(|_| {
+                let degree = gen_len_by(g, .., |g| gen_below(g, 4) == 0);
+                (0..degree).map(|_| gen_below(g, n as u64) as usize).collect()
+            })
+            .collect();
+        RefGraph(nodes)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let me = self.clone();
+        let n = self.0.len();
+        let remove_nodes = (0..n).rev().map(move |i| me.remove_node(i));
+        let me = self.clone();
+        let remove_refs = (0..n).flat_map(move |node| {
+            let me = me.clone();
+            (0..me.0[node].len()).map(move |i| me.remove_ref(node, i))
+        });
+        Box::new(remove_nodes.chain(remove_refs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RefGraph;
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn in_bounds(graph: &RefGraph) {
+        let n = graph.0.len();
+        assert!(graph.0.iter().flatten().all(|x| *x < n), "{:?}", graph);
+    }
+
+    fn has_cycle(graph: &RefGraph) -> bool {
+        // 0: unvisited, 1: on stack, 2: done
+        fn dfs(graph: &RefGraph, node: usize, color: &mut [u8]) -> bool {
+            color[node] = 1;
+            for next in graph.0[node].iter() {
+                let c = color[*next];
+                if c == 1 || (c == 0 && dfs(graph, *next, color)) {
+                    return true;
+                }
+            }
+            color[node] = 2;
+            false
+        }
+        let mut color = vec![0; graph.0.len()];
+        (0..graph.0.len()).any(|i| color[i] == 0 && dfs(graph, i, &mut color))
+    }
+
+    #[quickcheck]
+    fn refs_in_bounds(trial: RefGraph) {
+        in_bounds(&trial);
+        for x in trial.shrink() {
+            in_bounds(&x);
+        }
+    }
+
+    #[test]
+    fn cycles_occur() {
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let n = (0..100).filter(|_| has_cycle(&RefGraph::arbitrary(&mut g))).count();
+        assert!(n > 0, "n={}", n);
+    }
+
+    #[test]
+    fn remove_node_renumbers() {
+        let graph = RefGraph(vec![vec![1, 2], vec![0, 2], vec![2]]);
+        assert_eq!(graph.remove_node(1), RefGraph(vec![vec![1], vec![1]]));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::{gen_below, gen_between};
+use quickcheck::{Arbitrary, Gen};
+
+/// Sequences increasing in runs and periodically resetting, i.e., sawtooth.
+///
+/// Tests of sequence numbers and counters need values increasing and then
+/// wrapping around.
+///
+/// *   `arbitrary` generates runs of strictly increasing values,
+///     each run of at most [MAX_RUN](Self::MAX_RUN) values.
+///     Each run but the first one starts with a reset, i.e., a value lower than
+///     the last value of the previous run.
+///     The total length is exponentially distributed.
+/// *   `shrink` removes values from either end.
+///
+/// So, every (shrinked) sequence longer than [MAX_RUN](Self::MAX_RUN) has at least one reset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sawtooth(pub Vec<u32>);
+
+impl Sawtooth {
+    /// The maximum length of a run.
+    pub const MAX_RUN: usize = 8;
+}
+
+impl Arbitrary for Sawtooth {
+    fn arbitrary(g: &mut Gen) -> Self {
+        const MAX_STEP: u64 = 16;
+        let n = gen_len_by(g, .., |g| gen_below(g, 16) == 0);
+        let mut res = Vec::with_capacity(n);
+        let mut cur = gen_below(g, MAX_STEP) as u32;
+        while res.len() < n {
+            let run = gen_between(g, 2, Self::MAX_RUN as u64) as usize;
+            for _ in 0..run {
+                if res.len() >= n {
+                    break;
+                }
+                res.push(cur);
+                cur += gen_between(g, 1, MAX_STEP) as u32;
+            }
+            if res.len() >= n {
+                break;
+            }
+            let last = *res.last().unwrap();
+            cur = gen_below(g, u64::from(last)) as u32;
+        }
+        Sawtooth(res)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let xs = self.0.clone();
+        let n = xs.len();
+        let mut res = vec![];
+        let mut removed = n;
+        while removed > 0 {
+            res.push(Sawtooth(xs[..n - removed].to_vec()));
+            if removed < n {
+                res.push(Sawtooth(xs[removed..].to_vec()));
+            }
+            removed /= 2;
+        }
+        Box::new(res.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sawtooth;
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn check(xs: &[u32]) {
+        let mut run = 1;
+        for w in xs.windows(2) {
+            if w[0] < w[1] {
+                run += 1;
+            } else {
+                run = 1;
+            }
+            assert!(run <= Sawtooth::MAX_RUN, "{:?}", xs);
+        }
+        if xs.len() > Sawtooth::MAX_RUN {
+            assert!(xs.windows(2).any(|w| w[0] > w[1]), "{:?}", xs);
+        }
+    }
+
+    #[quickcheck]
+    fn sawtooth(trial: Sawtooth) {
+        check(&trial.0);
+        for x in trial.shrink() {
+            assert!(x.0.len() < trial.0.len());
+            check(&x.0);
+        }
+    }
+
+    #[test]
+    fn long_enough() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs: Vec<_> = (0..100).map(|_| Sawtooth::arbitrary(&mut g)).collect();
+        assert!(xs.iter().any(|x| x.0.len() > Sawtooth::MAX_RUN));
+    }
+}
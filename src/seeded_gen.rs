@@ -0,0 +1,135 @@
+use crate::Entropy;
+use quickcheck::Gen;
+use std::ops::{Deref, DerefMut};
+
+/// A reproducible generator, independent of quickcheck runs.
+///
+/// It wraps a quickcheck [Gen] seeded by `Gen::from_size_and_seed`,
+/// i.e., quickcheck's small PRNG seeded deterministically.
+/// So generators of this crate can be called with a fixed seed outside a
+/// quickcheck run, e.g., to reduce a flaky failure from a logged seed.
+///
+/// The sequence is the same for the same seed and size only with the same build.
+/// quickcheck's PRNG is `rand`'s `SmallRng`, which is portable neither across
+/// platforms nor across `rand` versions,
+/// so a seed logged on one CI machine may replay differently on another.
+/// For portable replay, use [SeededEntropy](crate::SeededEntropy) instead,
+/// which works with every generator not requiring [Gen].
+///
+/// `SeededGen` dereferences to [Gen],
+/// so it can be passed to any generator taking `&mut Gen`,
+/// and `&mut *g` is a plain `&mut Gen`.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_bytes, SeededGen};
+///
+/// let xs = gen_bytes(&mut SeededGen::new(42), b"abc.", b'.', ..);
+/// let ys = gen_bytes(&mut SeededGen::new(42), b"abc.", b'.', ..);
+/// assert_eq!(xs, ys);
+/// ```
+pub struct SeededGen {
+    seed: u64,
+    gen: Gen,
+}
+
+impl SeededGen {
+    /// The size of the wrapped [Gen], unless specified by [with_size](Self::with_size).
+    pub const DEFAULT_SIZE: usize = 100;
+
+    pub fn new(seed: u64) -> Self {
+        Self::with_size(seed, Self::DEFAULT_SIZE)
+    }
+
+    pub fn with_size(seed: u64, size: usize) -> Self {
+        Self {
+            seed,
+            gen: Gen::from_size_and_seed(size, seed),
+        }
+    }
+
+    /// The seed this generator started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Deref for SeededGen {
+    type Target = Gen;
+
+    fn deref(&self) -> &Gen {
+        &self.gen
+    }
+}
+
+impl DerefMut for SeededGen {
+    fn deref_mut(&mut self) -> &mut Gen {
+        &mut self.gen
+    }
+}
+
+impl Entropy for SeededGen {
+    fn next_u64(&mut self) -> u64 {
+        self.gen.next_u64()
+    }
+}
+
+/// [gen_bytes](crate::gen_bytes) with a fixed seed.
+pub fn gen_bytes_seeded<R>(
+    seed: u64,
+    alphabet: &[u8],
+    stopper: u8,
+    len_range: R,
+) -> Vec<u8>
+where R: std::ops::RangeBounds<usize>
+{
+    crate::gen_bytes(&mut SeededGen::new(seed), alphabet, stopper, len_range)
+}
+
+/// [shuffle](crate::shuffle) with a fixed seed.
+pub fn shuffle_seeded<T>(seed: u64, xs: &mut [T]) {
+    crate::shuffle(&mut SeededGen::new(seed), xs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SeededGen;
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn generate_all(seed: u64) -> impl PartialEq + std::fmt::Debug {
+        let mut g = SeededGen::new(seed);
+        let bytes = crate::gen_bytes(&mut g, b"abc.", b'.', ..);
+        let mut xs: Vec<_> = (0..10).collect();
+        crate::shuffle(&mut g, &mut xs);
+        let mixed = crate::gen_mixed_case(&mut g, b"abc", ..);
+        let truncated = crate::truncate(&mut g, b"abcdefg");
+        let keys = crate::IncreasingKeys::<1, 10>::arbitrary(&mut g);
+        (bytes, xs, mixed, truncated, keys)
+    }
+
+    #[quickcheck]
+    fn reproducible(seed: u64) {
+        assert_eq!(generate_all(seed), generate_all(seed));
+    }
+
+    #[quickcheck]
+    fn seeded_helpers(seed: u64) {
+        let xs = super::gen_bytes_seeded(seed, b"abc.", b'.', ..);
+        let ys = super::gen_bytes_seeded(seed, b"abc.", b'.', ..);
+        assert_eq!(xs, ys);
+        let mut xs: Vec<_> = (0..10).collect();
+        let mut ys = xs.clone();
+        super::shuffle_seeded(seed, &mut xs);
+        super::shuffle_seeded(seed, &mut ys);
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn seeds_differ() {
+        let xs: Vec<_> = (0..10)
+            .map(|seed| super::gen_bytes_seeded(seed, b"abc.", b'.', 8..))
+            .collect();
+        assert!(xs.iter().any(|x| *x != xs[0]));
+        assert_eq!(SeededGen::new(7).seed(), 7);
+    }
+}
@@ -53,37 +53,206 @@
 /// }
 /// ```
 ///
+/// Fields of tuple structs are specified by their indices.
+///
+/// ```rust
+/// use rs_quickcheck_util::{shrink_a_field, Unshrinkable};
+///
+/// #[derive(Debug, Clone)]
+/// struct Wrapper(Vec<u8>, u32);
+///
+/// let t = Wrapper(vec![1, 2, 3], 42);
+/// for x in shrink_a_field!(t, 1) {
+///     assert_eq!(x.0, t.0);
+///     assert!(x.1 < t.1);
+/// }
+/// let it = shrink_a_field!(
+///     t,
+///     0,
+///     |xs: &Vec<u8>| xs.iter().map(|x| Unshrinkable::new(*x)).collect::<Vec<_>>(),
+///     |xs: Vec<Unshrinkable<u8>>| xs.into_iter().map(|x| x.take()).collect::<Vec<_>>()
+/// );
+/// for x in it {
+///     assert!(x.0.len() < t.0.len());
+///     assert_eq!(x.1, t.1);
+/// }
+/// ```
+///
+/// Nested fields are specified by dotted paths, with or without wrapping.
+///
+/// ```rust
+/// use rs_quickcheck_util::{shrink_a_field, Unshrinkable};
+///
+/// #[derive(Debug, Clone)]
+/// struct Inner {
+///     value: u32,
+///     tags: Vec<u8>,
+/// }
+///
+/// #[derive(Debug, Clone)]
+/// struct Outer {
+///     name: String,
+///     inner: Inner,
+/// }
+///
+/// let t = Outer {
+///     name: "x".to_string(),
+///     inner: Inner { value: 42, tags: vec![1, 2] },
+/// };
+/// for x in shrink_a_field!(t, inner.value) {
+///     assert_eq!(x.name, t.name);
+///     assert_eq!(x.inner.tags, t.inner.tags);
+///     assert!(x.inner.value < t.inner.value);
+/// }
+/// let it = shrink_a_field!(
+///     t,
+///     inner.tags,
+///     |xs: &Vec<u8>| xs.iter().map(|x| Unshrinkable::new(*x)).collect::<Vec<_>>(),
+///     |xs: Vec<Unshrinkable<u8>>| xs.into_iter().map(|x| x.take()).collect::<Vec<_>>()
+/// );
+/// for x in it {
+///     assert!(x.inner.tags.iter().all(|y| t.inner.tags.contains(y)));
+///     assert_eq!(x.inner.value, t.inner.value);
+/// }
+/// ```
+///
 #[macro_export]
 macro_rules! shrink_a_field {
-    ($obj:expr, $field:ident) => {
+    ($obj:expr, $($field:tt).+) => {
         {
-            use quickcheck::Arbitrary;
             let me = $obj.clone();
-            me.clone().$field.shrink()
+            quickcheck::Arbitrary::shrink(&me.$($field).+)
                 .map(move |x| {
                     let mut res = me.clone();
-                    res.$field = x;
+                    res.$($field).+ = x;
                     res
                 })
         }
     };
-    ($obj:expr, $field:ident, $wrap_fn:expr, $unwrap_fn:expr) => {
+    ($obj:expr, $($field:tt).+, $wrap_fn:expr, $unwrap_fn:expr) => {
         {
-            use quickcheck::Arbitrary;
             let wrap_fn = $wrap_fn;
             let unwrap_fn = $unwrap_fn;
-            let xs = wrap_fn(&($obj.$field));
+            let xs = wrap_fn(&($obj.$($field).+));
             let me = $obj.clone();
-            xs.shrink()
+            quickcheck::Arbitrary::shrink(&xs)
                 .map(move |x| {
                     let mut res = me.clone();
-                    res.$field = unwrap_fn(x);
+                    res.$($field).+ = unwrap_fn(x);
                     res
                 })
         }
     }
 }
 
+/// Shrinks several fields of an object, one at a time.
+///
+/// `shrink_fields!(t, a, b, c)` results in a boxed iterator of type `T`,
+/// which first yields candidates with `a` shrinked and the other fields untouched,
+/// then those with `b` shrinked, and then those with `c` shrinked.
+/// Each field is shrinked as [shrink_a_field].
+/// So it can be the whole body of `shrink`.
+///
+/// ```rust
+/// use rs_quickcheck_util::shrink_fields;
+/// use quickcheck::{Arbitrary, Gen};
+///
+/// #[derive(Debug, Clone)]
+/// struct T {
+///     a: i64,
+///     b: Vec<u8>,
+/// }
+///
+/// impl Arbitrary for T {
+///     fn arbitrary(g: &mut Gen) -> Self {
+///         T {
+///             a: i64::arbitrary(g),
+///             b: Vec::arbitrary(g),
+///         }
+///     }
+///
+///     fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+///         shrink_fields!(self, a, b)
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! shrink_fields {
+    ($obj:expr $(, $field:tt)+ $(,)?) => {
+        {
+            let me = $obj.clone();
+            let it: Box<dyn Iterator<Item = _>> = Box::new(std::iter::empty());
+            $(
+                let it: Box<dyn Iterator<Item = _>> =
+                    Box::new(it.chain($crate::shrink_a_field!(me, $field)));
+            )+
+            it
+        }
+    };
+}
+
+/// Shrinks the payload of an enum value of a specific variant.
+///
+/// *   `shrink_variant!(v, E::V, a, b)` is for a tuple variant `E::V(a, b)`.
+/// *   `shrink_variant!(v, E::V { a, b })` is for a struct variant.
+///
+/// If `v` is of the variant, it results in a boxed iterator of type `E`,
+/// whose payload fields are shrinked one at a time,
+/// and the variant is kept.
+/// Otherwise, the iterator is empty.
+/// Fields are shrinked as a tuple, so there can be at most 8 of them.
+///
+/// ```rust
+/// use rs_quickcheck_util::shrink_variant;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Msg {
+///     Packet(Vec<u8>, u32),
+///     Ack { seq: u32 },
+///     Ping,
+/// }
+///
+/// let v = Msg::Packet(vec![1, 2], 7);
+/// for x in shrink_variant!(v, Msg::Packet, payload, seq) {
+///     assert!(matches!(x, Msg::Packet(_, _)));
+/// }
+/// for x in shrink_variant!(Msg::Ack { seq: 7 }, Msg::Ack { seq }) {
+///     assert!(matches!(x, Msg::Ack { seq } if seq < 7));
+/// }
+/// assert_eq!(shrink_variant!(Msg::Ping, Msg::Ack { seq }).count(), 0);
+/// ```
+#[macro_export]
+macro_rules! shrink_variant {
+    ($obj:expr, $($seg:ident)::+ { $($field:ident),+ $(,)? }) => {
+        {
+            #[allow(unreachable_patterns)]
+            let it: Box<dyn Iterator<Item = _>> = match &$obj {
+                $($seg)::+ { $($field),+ } => {
+                    let fields = ($($field.clone(),)+);
+                    Box::new(quickcheck::Arbitrary::shrink(&fields)
+                        .map(|($($field,)+)| $($seg)::+ { $($field),+ }))
+                }
+                _ => Box::new(std::iter::empty()),
+            };
+            it
+        }
+    };
+    ($obj:expr, $($seg:ident)::+ $(, $field:ident)+ $(,)?) => {
+        {
+            #[allow(unreachable_patterns)]
+            let it: Box<dyn Iterator<Item = _>> = match &$obj {
+                $($seg)::+ ( $($field),+ ) => {
+                    let fields = ($($field.clone(),)+);
+                    Box::new(quickcheck::Arbitrary::shrink(&fields)
+                        .map(|($($field,)+)| $($seg)::+ ( $($field),+ )))
+                }
+                _ => Box::new(std::iter::empty()),
+            };
+            it
+        }
+    };
+}
+
 /// Shrinks a mapping field whose key is determined by the value.
 ///
 /// For a mapping field, sometimes the key is determined by the value.
@@ -136,6 +305,50 @@ macro_rules! shrink_a_map_field {
     };
 }
 
+/// Shrinks a map field whose keys and values are independent.
+///
+/// Unlike [shrink_a_map_field], keys are not derived from values.
+/// `shrink_a_map_field_kv!(t, m)` shrinks `t.m` as a `Vec<(K, V)>` of its entries,
+/// which removes entries and shrinks keys and values,
+/// and then rebuilds the map by `collect`.
+///
+/// Shrinking keys may make them collide.
+/// Then the later entry wins, so the candidate may have fewer entries than
+/// the shrinked vector.
+///
+/// ```rust
+/// use rs_quickcheck_util::shrink_a_map_field_kv;
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Debug, Clone)]
+/// struct T {
+///     m: BTreeMap<u32, String>,
+/// }
+/// let t = T {
+///     m: [(10, "x".to_string()), (20, "y".to_string())].into(),
+/// };
+/// for x in shrink_a_map_field_kv!(t, m) {
+///     assert!(x.m.len() <= t.m.len());
+/// }
+/// ```
+#[macro_export]
+macro_rules! shrink_a_map_field_kv {
+    ($obj:expr, $field:tt) => {
+        {
+            let me = $obj.clone();
+            let xs: Vec<_> = me.$field.iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            quickcheck::Arbitrary::shrink(&xs)
+                .map(move |xs| {
+                    let mut res = me.clone();
+                    res.$field = xs.into_iter().collect();
+                    res
+                })
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use quickcheck_macros::*;
@@ -193,6 +406,150 @@ mod tests {
         }
     }
 
+    #[quickcheck]
+    fn shrink_map_kv(m: Vec<(u8, u16)>) {
+        let m = m.into_iter().take(16).collect();
+        let trial = B2 { m };
+        for x in shrink_a_map_field_kv!(trial, m) {
+            assert!(x.m.len() <= trial.m.len());
+            for (k, v) in x.m.iter() {
+                assert!(
+                    trial.m.iter().any(|(k0, v0)| k <= k0 && v <= v0),
+                    "{:?} is not from {:?}",
+                    (k, v),
+                    trial.m,
+                );
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct B2 {
+        m: BTreeMap<u8, u16>,
+    }
+
+    #[derive(Debug, Clone)]
+    struct C {
+        a: usize,
+        b: Vec<u8>,
+        c: String,
+    }
+
+    impl quickcheck::Arbitrary for C {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            Self {
+                a: usize::arbitrary(g),
+                b: Vec::arbitrary(g),
+                c: String::arbitrary(g),
+            }
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            shrink_fields!(self, a, b, c)
+        }
+    }
+
+    #[quickcheck]
+    fn shrink_fields_one_at_a_time(trial: C) {
+        use quickcheck::Arbitrary;
+        let mut last = 0;
+        for x in trial.shrink() {
+            let changed = [x.a != trial.a, x.b != trial.b, x.c != trial.c];
+            assert_eq!(changed.iter().filter(|y| **y).count(), 1, "{:?}", x);
+            let idx = changed.iter().position(|y| *y).unwrap();
+            // field by field
+            assert!(idx >= last);
+            last = idx;
+        }
+    }
+
+    #[derive(Debug, Clone, rs_quickcheck_util_derive::ShrinkFields)]
+    struct F {
+        a: usize,
+        #[shrink(skip)]
+        b: Vec<u8>,
+        c: String,
+    }
+
+    #[quickcheck]
+    fn derive_shrink_fields(a: usize, b: Vec<u8>, c: String) {
+        let trial = F { a, b, c };
+        let mut last = 0;
+        let mut count = 0;
+        for x in trial.shrink_fields() {
+            assert_eq!(x.b, trial.b);
+            let changed = [x.a != trial.a, x.c != trial.c];
+            assert_eq!(changed.iter().filter(|y| **y).count(), 1, "{:?}", x);
+            let idx = changed.iter().position(|y| *y).unwrap();
+            // field by field
+            assert!(idx >= last);
+            last = idx;
+            count += 1;
+        }
+        assert_eq!(count, shrink_fields!(trial, a, c).count());
+    }
+
+    #[derive(Debug, Clone, rs_quickcheck_util_derive::ShrinkFields)]
+    struct G(u32, #[shrink(skip)] u32);
+
+    #[quickcheck]
+    fn derive_shrink_tuple_fields(a: u32, b: u32) {
+        let trial = G(a, b);
+        for x in trial.shrink_fields() {
+            assert!(x.0 < trial.0);
+            assert_eq!(x.1, trial.1);
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct D(usize, String);
+
+    #[quickcheck]
+    fn shrink_tuple_fields(a: usize, b: String) {
+        let trial = D(a, b);
+        for x in shrink_fields!(trial, 0, 1) {
+            assert!((x.0 != trial.0) ^ (x.1 != trial.1), "{:?}", x);
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum E {
+        Pair(usize, String),
+        Named { a: u32, b: Vec<u8> },
+        Unit,
+    }
+
+    #[quickcheck]
+    fn shrink_tuple_variant(a: usize, b: String) {
+        for x in shrink_variant!(E::Pair(a, b.clone()), E::Pair, x, y) {
+            match x {
+                E::Pair(x, y) => assert!((x != a) ^ (y != b)),
+                _ => panic!("{:?}", x),
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn shrink_struct_variant(a: u32, b: Vec<u8>) {
+        let v = E::Named { a, b: b.clone() };
+        let xs: Vec<_> = shrink_variant!(v, E::Named { a, b }).collect();
+        for x in xs.iter() {
+            match x {
+                E::Named { a: x, b: y } => assert!((*x != a) ^ (*y != b)),
+                _ => panic!("{:?}", x),
+            }
+        }
+        if a > 0 || !b.is_empty() {
+            assert!(!xs.is_empty());
+        }
+    }
+
+    #[test]
+    fn shrink_mismatched_variant() {
+        assert_eq!(shrink_variant!(E::Unit, E::Pair, x, y).count(), 0);
+        assert_eq!(shrink_variant!(E::Pair(1, "a".to_string()), E::Named { a, b }).count(), 0);
+    }
+
     #[test]
     fn unshrinkable() {
         let z = A {
@@ -0,0 +1,57 @@
+/// Drops shrinked candidates which violate an invariant.
+///
+/// Candidates failing `pred` are discarded lazily,
+/// so no test run is wasted on invalid candidates.
+/// It composes with iterators from [shrink_a_field](crate::shrink_a_field) and the like.
+///
+/// ```rust
+/// use rs_quickcheck_util::{shrink_a_field, shrink_filter};
+///
+/// #[derive(Debug, Clone)]
+/// struct Interval {
+///     lo: u32,
+///     hi: u32,
+/// }
+/// let t = Interval { lo: 10, hi: 20 };
+/// // shrinking `hi` alone may break `lo <= hi`
+/// for x in shrink_filter(shrink_a_field!(t, hi), |x| x.lo <= x.hi) {
+///     assert!(x.lo <= x.hi);
+/// }
+/// ```
+pub fn shrink_filter<T, I, P>(iter: I, pred: P) -> impl Iterator<Item = T>
+where
+    I: Iterator<Item = T>,
+    P: Fn(&T) -> bool,
+{
+    iter.filter(move |x| pred(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[derive(Debug, Clone)]
+    struct Interval {
+        lo: u32,
+        hi: u32,
+    }
+
+    #[quickcheck]
+    fn only_valid(a: u32, b: u32) {
+        let trial = if a <= b {
+            Interval { lo: a, hi: b }
+        } else {
+            Interval { lo: b, hi: a }
+        };
+        let total = crate::shrink_a_field!(trial, hi).count();
+        let valid: Vec<_> = super::shrink_filter(
+            crate::shrink_a_field!(trial, hi),
+            |x| x.lo <= x.hi,
+        ).collect();
+        for x in valid.iter() {
+            assert!(x.lo <= x.hi, "{:?}", x);
+        }
+        let invalid = crate::shrink_a_field!(trial, hi).filter(|x| x.lo > x.hi).count();
+        assert_eq!(valid.len() + invalid, total);
+    }
+}
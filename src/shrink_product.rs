@@ -0,0 +1,60 @@
+use quickcheck::Arbitrary;
+
+/// The maximum number of shrinked candidates taken from each component by [shrink_product].
+pub const SHRINK_PRODUCT_CAP: usize = 8;
+
+/// Shrinks two values simultaneously.
+///
+/// Shrinking fields one at a time misses counterexamples where
+/// both fields have to be smaller at the same time.
+/// This function yields the cartesian product of shrinked candidates of `a` and `b`.
+/// To avoid explosion, only the first [SHRINK_PRODUCT_CAP] candidates of each are taken,
+/// so there are at most `SHRINK_PRODUCT_CAP * SHRINK_PRODUCT_CAP` pairs.
+///
+/// Every yielded pair has both components shrinked.
+/// Combine it with per-field shrinking,
+/// e.g., [shrink_a_field](crate::shrink_a_field), to cover pairs with one component kept.
+///
+/// ```rust
+/// use rs_quickcheck_util::shrink_product;
+///
+/// let xs: Vec<_> = shrink_product(&4u32, &true).collect();
+/// assert!(xs.contains(&(0, false)));
+/// ```
+pub fn shrink_product<A, B>(a: &A, b: &B) -> impl Iterator<Item = (A, B)>
+where
+    A: Arbitrary + Clone,
+    B: Arbitrary + Clone,
+{
+    let xs: Vec<A> = a.shrink().take(SHRINK_PRODUCT_CAP).collect();
+    let ys: Vec<B> = b.shrink().take(SHRINK_PRODUCT_CAP).collect();
+    xs.into_iter()
+        .flat_map(move |x| ys.clone().into_iter().map(move |y| (x.clone(), y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SHRINK_PRODUCT_CAP;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn both_smaller(a: u32, b: Vec<u8>) {
+        let xs: Vec<_> = super::shrink_product(&a, &b).collect();
+        assert!(xs.len() <= SHRINK_PRODUCT_CAP * SHRINK_PRODUCT_CAP);
+        for (x, y) in xs.iter() {
+            assert!(*x < a, "left={}, right={}", x, a);
+            assert_ne!(y, &b);
+        }
+        if a > 0 && !b.is_empty() {
+            assert!(!xs.is_empty());
+        }
+    }
+
+    #[test]
+    fn reaches_joint_counterexample() {
+        // fails only when both are small
+        let prop = |x: u32, y: u32| x >= 10 || y >= 10;
+        let xs: Vec<_> = super::shrink_product(&100u32, &100u32).collect();
+        assert!(xs.iter().any(|(x, y)| !prop(*x, *y)));
+    }
+}
@@ -0,0 +1,163 @@
+use quickcheck::{Arbitrary, Gen};
+use std::fmt::Debug;
+
+/// Primitive integer types, i.e., `u8` to `u128`, `i8` to `i128`, `usize` and `isize`.
+///
+/// Its methods are implementation details of this crate.
+pub trait PrimitiveInt: Copy + Ord + Debug + 'static {
+    #[doc(hidden)]
+    fn __to_bits(self) -> u128;
+    #[doc(hidden)]
+    fn __from_bits(x: u128) -> Self;
+}
+
+macro_rules! impl_primitive_int {
+    ($($t:ty),*) => {
+        $(
+            impl PrimitiveInt for $t {
+                fn __to_bits(self) -> u128 {
+                    self as u128
+                }
+
+                fn __from_bits(x: u128) -> Self {
+                    x as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// `|x - y|`, which never overflows.
+pub(crate) fn distance<T: PrimitiveInt>(x: T, y: T) -> u128 {
+    if x >= y {
+        x.__to_bits().wrapping_sub(y.__to_bits())
+    } else {
+        y.__to_bits().wrapping_sub(x.__to_bits())
+    }
+}
+
+/// Moves `from` toward `to` by `d`, which must be no more than their distance.
+pub(crate) fn step<T: PrimitiveInt>(from: T, to: T, d: u128) -> T {
+    if to >= from {
+        T::__from_bits(from.__to_bits().wrapping_add(d))
+    } else {
+        T::__from_bits(from.__to_bits().wrapping_sub(d))
+    }
+}
+
+/// Shrinks an integer toward `target`, instead of zero.
+///
+/// Candidates halve the distance to `target` one after another,
+/// so they approach `target` strictly monotonically, and the last one is `target` itself.
+/// Nothing is yielded if `value` is already `target`.
+///
+/// ```rust
+/// use rs_quickcheck_util::shrink_towards;
+///
+/// let xs: Vec<u16> = shrink_towards(8090, 8080).collect();
+/// assert_eq!(xs, vec![8085, 8082, 8081, 8080]);
+/// ```
+pub fn shrink_towards<T: PrimitiveInt>(value: T, target: T) -> impl Iterator<Item = T> {
+    let d = distance(value, target);
+    let first = if d == 0 { None } else { Some(d >> 1) };
+    std::iter::successors(first, |k| if *k == 0 { None } else { Some(k >> 1) })
+        .map(move |k| step(target, value, k))
+}
+
+/// An integer shrinking by [shrink_towards].
+///
+/// It is for the wrap/unwrap pattern of [shrink_a_field](crate::shrink_a_field).
+/// Its `arbitrary` generates `T::arbitrary` shrinking toward zero.
+///
+/// ```rust
+/// use rs_quickcheck_util::{shrink_a_field, Towards};
+///
+/// #[derive(Debug, Clone)]
+/// struct Config {
+///     port: u16,
+/// }
+/// let t = Config { port: 9000 };
+/// let it = shrink_a_field!(t, port, |x: &u16| Towards::new(*x, 8080), |x: Towards<u16>| x.take());
+/// for x in it {
+///     assert!((8080..9000).contains(&x.port));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Towards<T> {
+    pub value: T,
+    pub target: T,
+}
+
+impl<T: PrimitiveInt> Towards<T> {
+    pub fn new(value: T, target: T) -> Self {
+        Self { value, target }
+    }
+
+    pub fn take(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PrimitiveInt + Arbitrary> Arbitrary for Towards<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(T::arbitrary(g), T::__from_bits(0))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let target = self.target;
+        Box::new(shrink_towards(self.value, target).map(move |x| Self::new(x, target)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::distance;
+    use quickcheck_macros::*;
+
+    fn check<T: super::PrimitiveInt>(value: T, target: T) {
+        let xs: Vec<T> = super::shrink_towards(value, target).collect();
+        let mut last = distance(value, target);
+        for x in xs.iter() {
+            let d = distance(*x, target);
+            assert!(d < last, "value={:?}, target={:?}, xs={:?}", value, target, xs);
+            assert!(distance(*x, value) <= distance(value, target));
+            last = d;
+        }
+        assert_eq!(xs.last().copied().unwrap_or(value), target);
+        assert!(xs.len() <= 129);
+    }
+
+    #[quickcheck]
+    fn approaching_i8(value: i8, target: i8) {
+        check(value, target);
+    }
+
+    #[quickcheck]
+    fn approaching_u64(value: u64, target: u64) {
+        check(value, target);
+    }
+
+    #[quickcheck]
+    fn approaching_i128(value: i128, target: i128) {
+        check(value, target);
+    }
+
+    #[test]
+    fn extremes() {
+        check(i128::MIN, i128::MAX);
+        check(i128::MAX, i128::MIN);
+        check(u8::MAX, 0);
+        check(isize::MIN, 0);
+        assert_eq!(super::shrink_towards(5u32, 5).count(), 0);
+    }
+
+    #[quickcheck]
+    fn towards_arbitrary(x: super::Towards<i32>) {
+        for y in quickcheck::Arbitrary::shrink(&x) {
+            assert_eq!(y.target, 0);
+            assert!(y.value.unsigned_abs() < x.value.unsigned_abs());
+        }
+    }
+}
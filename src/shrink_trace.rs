@@ -0,0 +1,74 @@
+use quickcheck::Arbitrary;
+use std::fmt::Debug;
+
+/// Records the path greedy shrinking takes, for debugging minimization.
+///
+/// Starting from `start`, on which `prop` fails (i.e., returns `false`),
+/// this function shrinks greedily as quickcheck does,
+/// i.e., always steps to the first failing shrinked candidate,
+/// until no candidate fails.
+/// It returns every value it stepped to, from `start` to the minimal failing value.
+///
+/// If `prop` holds on `start`, the trace consists of `start` only.
+///
+/// ```rust
+/// use rs_quickcheck_util::shrink_trace;
+///
+/// let trace = shrink_trace(1000u32, |x| *x < 100);
+/// assert_eq!(trace.first(), Some(&1000));
+/// assert_eq!(trace.last(), Some(&100));
+/// ```
+pub fn shrink_trace<T, P>(start: T, prop: P) -> Vec<T>
+where
+    T: Arbitrary + Clone + Debug,
+    P: Fn(&T) -> bool,
+{
+    let mut res = vec![start];
+    if prop(&res[0]) {
+        return res;
+    }
+    loop {
+        let next = res.last().unwrap().shrink().find(|x| !prop(x));
+        match next {
+            Some(x) => res.push(x),
+            None => break,
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn prop(xs: &[u32]) -> bool {
+        xs.len() < 3 && xs.iter().all(|x| *x < 500)
+    }
+
+    fn metric(xs: &[u32]) -> (usize, u64) {
+        (xs.len(), xs.iter().map(|x| u64::from(*x)).sum())
+    }
+
+    #[quickcheck]
+    fn decreasing(start: Vec<u32>) {
+        let trace = super::shrink_trace(start.clone(), |xs: &Vec<u32>| prop(xs));
+        assert_eq!(trace[0], start);
+        if prop(&start) {
+            assert_eq!(trace.len(), 1);
+            return;
+        }
+        for w in trace.windows(2) {
+            assert!(metric(&w[1]) < metric(&w[0]), "{:?}", trace);
+        }
+        let last = trace.last().unwrap();
+        assert!(!prop(last));
+        assert!(last.shrink().all(|x| prop(&x)), "{:?}", last);
+    }
+
+    #[test]
+    fn ends_at_minimum() {
+        let trace = super::shrink_trace(vec![5, 500, 7], |xs: &Vec<u32>| prop(xs));
+        assert_eq!(trace.last(), Some(&vec![500]));
+    }
+}
@@ -1,10 +1,197 @@
-use quickcheck::{Gen, Arbitrary};
+use crate::Entropy;
 
 /// Uniformly shuffle a slice.
-pub fn shuffle<T>(g: &mut Gen, xs: &mut [T]) {
+///
+/// It is Fisher–Yates shuffle,
+/// with swap indices chosen by rejection sampling, so there is no modulo bias.
+/// So each of the $n!$ permutations is equally likely.
+///
+/// It is generic over [Entropy],
+/// so it works with a quickcheck [Gen](quickcheck::Gen) as well as standalone sources.
+pub fn shuffle<E, T>(g: &mut E, xs: &mut [T])
+where E: Entropy + ?Sized
+{
     let n = xs.len();
     for i in 0..n {
-        let with = i + usize::arbitrary(g) % (n - i);
+        let with = i + g.choose_index(n - i);
         xs.swap(i, with);
     }
 }
+
+/// Returns a shuffled copy of a slice.
+///
+/// Please refer to [shuffle].
+pub fn shuffled<E, T>(g: &mut E, xs: &[T]) -> Vec<T>
+where
+    E: Entropy + ?Sized,
+    T: Clone,
+{
+    let mut res = xs.to_vec();
+    shuffle(g, &mut res);
+    res
+}
+
+/// Chooses `k` distinct elements uniformly without replacement.
+///
+/// Elements at distinct positions are chosen,
+/// and `k` is clamped to `xs.len()`.
+/// So `k >= xs.len()` yields a shuffled copy of `xs`.
+/// It is a partial Fisher–Yates shuffle, in $O(n)$.
+pub fn sample_k<E, T>(g: &mut E, xs: &[T], k: usize) -> Vec<T>
+where
+    E: Entropy + ?Sized,
+    T: Clone,
+{
+    let n = xs.len();
+    let k = k.min(n);
+    let mut idx: Vec<usize> = (0..n).collect();
+    for i in 0..k {
+        let with = i + g.choose_index(n - i);
+        idx.swap(i, with);
+    }
+    idx[..k].iter().map(|i| xs[*i].clone()).collect()
+}
+
+/// Shuffles a slice, so that heavier elements tend to be earlier.
+///
+/// It is weighted sampling without replacement:
+/// each position is filled by one of the remaining elements,
+/// chosen with possibility proportional to its weight.
+/// Unlike [shuffle], permutations are *not* equally likely,
+/// unless all weights are equal.
+/// Elements of zero weight are placed after all others, in uniformly random order.
+///
+/// It panics if `weights` and `xs` differ in length.
+///
+/// ```rust
+/// use rs_quickcheck_util::weighted_shuffle;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let mut tasks = ["urgent", "normal", "idle"];
+/// weighted_shuffle(&mut g, &mut tasks, &[100, 10, 0]);
+/// assert_eq!(tasks[2], "idle");
+/// ```
+pub fn weighted_shuffle<E, T>(g: &mut E, xs: &mut [T], weights: &[u32])
+where E: Entropy + ?Sized
+{
+    assert_eq!(
+        xs.len(),
+        weights.len(),
+        "weighted_shuffle: {} elements but {} weights",
+        xs.len(),
+        weights.len(),
+    );
+    let mut weights = weights.to_vec();
+    let n = xs.len();
+    for i in 0..n {
+        if weights[i..].iter().all(|w| *w == 0) {
+            shuffle(g, &mut xs[i..]);
+            return;
+        }
+        let with = i + crate::__weighted_index(g, &weights[i..]);
+        xs.swap(i, with);
+        weights.swap(i, with);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn shuffled_keeps_elements(xs: Vec<u8>) {
+        let mut g = quickcheck::Gen::new(32);
+        let mut ys = super::shuffled(&mut g, &xs);
+        let mut xs = xs;
+        xs.sort_unstable();
+        ys.sort_unstable();
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn chi_squared() {
+        const N: usize = 24000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let samples: Vec<Vec<u8>> = (0..N)
+            .map(|_| {
+                let mut xs = vec![0, 1, 2, 3];
+                super::shuffle(&mut g, &mut xs);
+                xs
+            })
+            .collect();
+        let hist = crate::permutation_histogram(&samples);
+        assert_eq!(hist.len(), 24);
+        let expected = N as f64 / 24.0;
+        let chi2: f64 = hist.values()
+            .map(|x| {
+                let d = *x as f64 - expected;
+                d * d / expected
+            })
+            .sum();
+        // the critical value of 23 degrees of freedom at significance 0.001
+        assert!(chi2 < 49.73, "chi2={}", chi2);
+    }
+
+    #[quickcheck]
+    fn sample_distinct(n: u8, k: u8) {
+        let xs: Vec<u8> = (0..n).collect();
+        let mut g = quickcheck::Gen::new(32);
+        let mut ys = super::sample_k(&mut g, &xs, usize::from(k));
+        assert_eq!(ys.len(), usize::from(k.min(n)));
+        ys.sort_unstable();
+        ys.dedup();
+        assert_eq!(ys.len(), usize::from(k.min(n)));
+        assert!(ys.iter().all(|y| *y < n));
+    }
+
+    #[quickcheck]
+    fn sample_all(xs: Vec<u8>, extra: u8) {
+        let mut g = quickcheck::Gen::new(32);
+        let mut ys = super::sample_k(&mut g, &xs, xs.len() + usize::from(extra));
+        let mut xs = xs;
+        xs.sort_unstable();
+        ys.sort_unstable();
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn heavier_earlier() {
+        const N: usize = 10000;
+        const WEIGHTS: &[u32] = &[1, 4, 0, 16, 2];
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut total = [0usize; 5];
+        for _ in 0..N {
+            let mut xs = [0, 1, 2, 3, 4];
+            super::weighted_shuffle(&mut g, &mut xs, WEIGHTS);
+            for (pos, x) in xs.iter().enumerate() {
+                total[*x] += pos;
+            }
+        }
+        // by weights, descending
+        let order = [3, 1, 4, 0, 2];
+        for w in order.windows(2) {
+            assert!(total[w[0]] < total[w[1]], "{:?}", total);
+        }
+        // zero weight always last
+        assert_eq!(total[2], 4 * N);
+    }
+
+    #[quickcheck]
+    fn weighted_shuffle_keeps_elements(xs: Vec<(u8, u32)>) {
+        let mut g = quickcheck::Gen::new(32);
+        let weights: Vec<u32> = xs.iter().map(|(_, w)| *w % 4).collect();
+        let mut ys: Vec<u8> = xs.iter().map(|(x, _)| *x).collect();
+        super::weighted_shuffle(&mut g, &mut ys, &weights);
+        let mut xs: Vec<u8> = xs.iter().map(|(x, _)| *x).collect();
+        xs.sort_unstable();
+        ys.sort_unstable();
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    #[should_panic(expected = "elements but")]
+    fn weighted_shuffle_mismatched() {
+        let mut g = quickcheck::Gen::new(32);
+        super::weighted_shuffle(&mut g, &mut [1, 2], &[1]);
+    }
+}
@@ -0,0 +1,61 @@
+use quickcheck::Gen;
+
+/// Method forms of slice-related generators, mirroring `SliceRandom` of `rand`.
+///
+/// ```rust
+/// use rs_quickcheck_util::SliceGenExt;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let mut xs = [1, 2, 3, 4];
+/// xs.shuffle(&mut g);
+/// assert!(xs.choose(&mut g).is_some());
+/// ```
+pub trait SliceGenExt {
+    type Item;
+
+    /// Uniformly shuffles the slice. Please refer to [shuffle](crate::shuffle).
+    fn shuffle(&mut self, g: &mut Gen);
+
+    /// Uniformly chooses an element, or `None` if the slice is empty.
+    fn choose(&self, g: &mut Gen) -> Option<&Self::Item>;
+}
+
+impl<T> SliceGenExt for [T] {
+    type Item = T;
+
+    fn shuffle(&mut self, g: &mut Gen) {
+        crate::shuffle(g, self)
+    }
+
+    fn choose(&self, g: &mut Gen) -> Option<&T> {
+        g.choose(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SliceGenExt;
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn same_as_free_functions(seed: u64, xs: Vec<u32>) {
+        let mut g0 = quickcheck::Gen::from_size_and_seed(32, seed);
+        let mut g1 = quickcheck::Gen::from_size_and_seed(32, seed);
+
+        let mut ys0 = xs.clone();
+        let mut ys1 = xs.clone();
+        crate::shuffle(&mut g0, &mut ys0);
+        ys1.shuffle(&mut g1);
+        assert_eq!(ys0, ys1);
+
+        assert_eq!(g0.choose(&xs), xs.choose(&mut g1));
+        assert_eq!(g0.choose(&xs), xs[..].choose(&mut g1));
+    }
+
+    #[test]
+    fn empty() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs: [u32; 0] = [];
+        assert_eq!(xs.choose(&mut g), None);
+    }
+}
@@ -0,0 +1,84 @@
+use quickcheck::{Arbitrary, Gen};
+use std::ops::Deref;
+
+/// Generates an ascending vector of arbitrary elements.
+///
+/// It is [gen_vec](crate::gen_vec) followed by sorting,
+/// so the length is distributed as [gen_vec](crate::gen_vec).
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_sorted_vec;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_sorted_vec::<u32, _>(&mut g, ..10);
+/// assert!(xs.windows(2).all(|w| w[0] <= w[1]));
+/// ```
+pub fn gen_sorted_vec<T, R>(g: &mut Gen, len_range: R) -> Vec<T>
+where
+    T: Arbitrary + Ord,
+    R: std::ops::RangeBounds<usize>,
+{
+    let mut res = crate::gen_vec(g, len_range);
+    res.sort();
+    res
+}
+
+/// A vector which is always ascending.
+///
+/// *   `arbitrary` generates by [gen_sorted_vec].
+/// *   `shrink` shrinks as `Vec<T>`, and then sorts every candidate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortedVec<T>(pub Vec<T>);
+
+impl<T> Deref for SortedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: Arbitrary + Ord> Arbitrary for SortedVec<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        SortedVec(gen_sorted_vec(g, ..))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink().map(|mut xs| {
+            xs.sort();
+            SortedVec(xs)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+
+    fn is_sorted<T: Ord>(xs: &[T]) -> bool {
+        xs.windows(2).all(|w| w[0] <= w[1])
+    }
+
+    #[quickcheck]
+    fn sorted_and_in_range(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_sorted_vec::<i32, _>(&mut g, a..=b);
+        assert!(is_sorted(&xs), "{:?}", xs);
+        assert!(xs.len() >= a, "left={}, right={}", xs.len(), a);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+    }
+
+    #[quickcheck]
+    fn shrink_sorted(trial: super::SortedVec<i32>) {
+        assert!(is_sorted(&trial));
+        for x in trial.shrink() {
+            assert!(is_sorted(&x), "{:?}", x);
+        }
+    }
+}
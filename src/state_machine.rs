@@ -0,0 +1,172 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use crate::Entropy;
+
+/// A state machine whose transitions have preconditions.
+///
+/// Please refer to [gen_ops] for how to use it.
+pub trait StateMachine {
+    type State;
+    type Op;
+
+    /// Legal operations in the given state, each with its weight.
+    fn transitions(&self, state: &Self::State) -> Vec<(Self::Op, u32)>;
+
+    /// Applies an operation to the state.
+    fn apply(&self, state: &mut Self::State, op: &Self::Op);
+}
+
+/// Generates a sequence of operations, each of which is legal in its context.
+///
+/// Starting from `initial`, it walks the state machine `sm` by repeatedly
+/// choosing one of its legal operations with possibility proportional to the weight,
+/// and applying it.
+///
+/// The number of operations is exponentially distributed with stop possibility 1/16.
+/// Please refer to [gen_bytes](crate::gen_bytes) for details on the length range.
+/// Walking stops early at a state without any legal operation of positive weight,
+/// even if the sequence is shorter than the lower bound of `len_range`.
+///
+/// ```rust
+/// use rs_quickcheck_util::{gen_ops, StateMachine};
+///
+/// struct Stack;
+///
+/// #[derive(Debug, Clone)]
+/// enum Op {
+///     Push,
+///     Pop,
+/// }
+///
+/// impl StateMachine for Stack {
+///     type State = usize;
+///     type Op = Op;
+///
+///     fn transitions(&self, depth: &usize) -> Vec<(Op, u32)> {
+///         if *depth == 0 {
+///             vec![(Op::Push, 1)]
+///         } else {
+///             vec![(Op::Push, 1), (Op::Pop, 1)]
+///         }
+///     }
+///
+///     fn apply(&self, depth: &mut usize, op: &Op) {
+///         match op {
+///             Op::Push => *depth += 1,
+///             Op::Pop => *depth -= 1,
+///         }
+///     }
+/// }
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let ops = gen_ops(&mut g, &Stack, 0, ..);
+/// ```
+pub fn gen_ops<E, M, R>(
+    g: &mut E,
+    sm: &M,
+    initial: M::State,
+    len_range: R,
+) -> Vec<M::Op>
+where
+    E: Entropy + ?Sized,
+    M: StateMachine,
+    R: std::ops::RangeBounds<usize>,
+{
+    let len = gen_len_by(g, len_range, |g| gen_below(g, 16) == 0);
+    let mut state = initial;
+    let mut res = Vec::with_capacity(len);
+    while res.len() < len {
+        let mut transitions = sm.transitions(&state);
+        let weights: Vec<u32> = transitions.iter().map(|(_, w)| *w).collect();
+        if weights.iter().all(|w| *w == 0) {
+            break;
+        }
+        let idx = crate::__weighted_index(g, &weights);
+        let op = transitions.swap_remove(idx).0;
+        sm.apply(&mut state, &op);
+        res.push(op);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StateMachine;
+    use quickcheck_macros::*;
+
+    const MAX: u32 = 5;
+
+    /// A counter in `0..=MAX`.
+    struct Counter;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Op {
+        Inc,
+        Dec,
+        Reset,
+    }
+
+    impl StateMachine for Counter {
+        type State = u32;
+        type Op = Op;
+
+        fn transitions(&self, state: &u32) -> Vec<(Op, u32)> {
+            let mut res = vec![(Op::Reset, 1)];
+            if *state < MAX {
+                res.push((Op::Inc, 4));
+            }
+            if *state > 0 {
+                res.push((Op::Dec, 2));
+            }
+            res
+        }
+
+        fn apply(&self, state: &mut u32, op: &Op) {
+            match op {
+                Op::Inc => *state += 1,
+                Op::Dec => *state -= 1,
+                Op::Reset => *state = 0,
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn all_legal(initial: u8, a: u8, b: u8) {
+        let initial = u32::from(initial) % (MAX + 1);
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let ops = super::gen_ops(&mut g, &Counter, initial, a..=b);
+        assert!(ops.len() >= a, "left={}, right={}", ops.len(), a);
+        assert!(ops.len() <= b, "left={}, right={}", ops.len(), b);
+        let mut state = initial;
+        for op in ops.iter() {
+            assert!(Counter.transitions(&state).iter().any(|(x, _)| x == op), "{:?}", ops);
+            Counter.apply(&mut state, op);
+            assert!(state <= MAX);
+        }
+    }
+
+    /// A machine without any legal operation.
+    struct Dead;
+
+    impl StateMachine for Dead {
+        type State = ();
+        type Op = ();
+
+        fn transitions(&self, _: &()) -> Vec<((), u32)> {
+            vec![((), 0)]
+        }
+
+        fn apply(&self, _: &mut (), _: &()) {}
+    }
+
+    #[test]
+    fn dead_end() {
+        let mut g = quickcheck::Gen::new(32);
+        assert!(super::gen_ops(&mut g, &Dead, (), 10..).is_empty());
+    }
+}
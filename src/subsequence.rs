@@ -0,0 +1,82 @@
+use crate::Entropy;
+
+/// Chooses a random subsequence, keeping the relative order of elements.
+///
+/// Each element is included independently with possibility 1/2.
+/// So the length varies, and an empty slice yields an empty subsequence.
+/// Unlike [sample_k](crate::sample_k) and [shuffled](crate::shuffled),
+/// chosen elements keep their original order.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_subsequence;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_subsequence(&mut g, &[1, 2, 3, 4, 5]);
+/// assert!(xs.windows(2).all(|w| w[0] < w[1]));
+/// ```
+pub fn gen_subsequence<E, T>(g: &mut E, xs: &[T]) -> Vec<T>
+where
+    E: Entropy + ?Sized,
+    T: Clone,
+{
+    gen_subsequence_with_p(g, xs, 0.5)
+}
+
+/// [gen_subsequence] including each element with possibility `p`.
+///
+/// `p` must be in $[0, 1]$.
+pub fn gen_subsequence_with_p<E, T>(g: &mut E, xs: &[T], p: f64) -> Vec<T>
+where
+    E: Entropy + ?Sized,
+    T: Clone,
+{
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "gen_subsequence_with_p: p={} is not in [0, 1]",
+        p,
+    );
+    xs.iter()
+        .filter(|_| crate::gen_bool(g, p))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    fn is_subsequence<T: PartialEq>(xs: &[T], of: &[T]) -> bool {
+        let mut it = of.iter();
+        xs.iter().all(|x| it.any(|y| y == x))
+    }
+
+    #[quickcheck]
+    fn order_preserving_subset(xs: Vec<u8>) {
+        let mut g = quickcheck::Gen::new(32);
+        let ys = super::gen_subsequence(&mut g, &xs);
+        assert!(is_subsequence(&ys, &xs), "{:?} of {:?}", ys, xs);
+    }
+
+    #[test]
+    fn edge_cases() {
+        let mut g = quickcheck::Gen::new(32);
+        let empty: &[u8] = &[];
+        assert!(super::gen_subsequence(&mut g, empty).is_empty());
+        let xs: Vec<u32> = (0..100).collect();
+        assert!(super::gen_subsequence_with_p(&mut g, &xs, 0.0).is_empty());
+        assert_eq!(super::gen_subsequence_with_p(&mut g, &xs, 1.0), xs);
+    }
+
+    #[test]
+    fn varying_length() {
+        const N: usize = 1000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let xs: Vec<u32> = (0..16).collect();
+        let lens: Vec<usize> = (0..N)
+            .map(|_| super::gen_subsequence(&mut g, &xs).len())
+            .collect();
+        let mean = lens.iter().sum::<usize>() as f64 / N as f64;
+        assert!((mean - 8.0).abs() < 0.5, "mean={}", mean);
+        assert!(lens.iter().any(|x| *x != lens[0]));
+    }
+}
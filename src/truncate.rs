@@ -0,0 +1,38 @@
+use crate::random::gen_below;
+use crate::Entropy;
+
+/// Truncates an input at a random point.
+///
+/// It returns a prefix of `input`, whose length is uniformly distributed in
+/// $[0, n]$ where $n$ is the length of `input`.
+/// Combined with a generator of valid inputs, it covers every truncation point,
+/// which is useful to test handling of partial reads and truncated inputs.
+pub fn truncate<E>(g: &mut E, input: &[u8]) -> Vec<u8>
+where E: Entropy + ?Sized
+{
+    let len = gen_below(g, input.len() as u64 + 1) as usize;
+    input[..len].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+
+    #[quickcheck]
+    fn prefix(input: Vec<u8>) {
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::truncate(&mut g, &input);
+        assert!(input.starts_with(&xs), "input={:?}, truncated={:?}", input, xs);
+    }
+
+    #[test]
+    fn both_ends_occur() {
+        const INPUT: &[u8] = b"abc";
+        let mut g = quickcheck::Gen::new(32);
+        let lens: Vec<_> = (0..1000)
+            .map(|_| super::truncate(&mut g, INPUT).len())
+            .collect();
+        assert!(lens.contains(&0));
+        assert!(lens.contains(&INPUT.len()));
+    }
+}
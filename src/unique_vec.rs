@@ -0,0 +1,76 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+use std::collections::BTreeSet;
+
+/// Generates a vector of arbitrary elements without duplicates.
+///
+/// The length is sampled as [gen_vec](crate::gen_vec).
+/// Then candidates are generated by `T::arbitrary`, and duplicated ones are dropped,
+/// until the sampled length is reached.
+/// Elements are kept in the order they are generated.
+///
+/// At most [MAX_ATTEMPTS_PER_ELEMENT] candidates per element are tried.
+/// So the result may fall short of the sampled length, even below the lower bound
+/// of `len_range`, if the domain of `T` is small, e.g., `bool` or `u8`.
+///
+/// ```rust
+/// use rs_quickcheck_util::gen_unique_vec;
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let xs = gen_unique_vec::<u64, _>(&mut g, ..);
+/// let mut ys = xs.clone();
+/// ys.sort_unstable();
+/// ys.dedup();
+/// assert_eq!(xs.len(), ys.len());
+/// ```
+pub fn gen_unique_vec<T, R>(g: &mut Gen, len_range: R) -> Vec<T>
+where
+    T: Arbitrary + Ord + Clone,
+    R: std::ops::RangeBounds<usize>,
+{
+    let len = gen_len_by(g, len_range, |g| gen_below(g, 16) == 0);
+    let mut seen = BTreeSet::new();
+    let mut res = Vec::with_capacity(len);
+    for _ in 0..len.saturating_mul(MAX_ATTEMPTS_PER_ELEMENT) {
+        if res.len() >= len {
+            break;
+        }
+        let x = T::arbitrary(g);
+        if seen.insert(x.clone()) {
+            res.push(x);
+        }
+    }
+    res
+}
+
+/// The maximum number of candidates tried per element by [gen_unique_vec].
+pub const MAX_ATTEMPTS_PER_ELEMENT: usize = 8;
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::*;
+    use std::collections::BTreeSet;
+
+    #[quickcheck]
+    fn no_duplicates(a: u8, b: u8) {
+        let (a, b) = if a < b {
+            (a as usize, b as usize)
+        } else {
+            (b as usize, a as usize)
+        };
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_unique_vec::<u32, _>(&mut g, a..=b);
+        let set: BTreeSet<_> = xs.iter().collect();
+        assert_eq!(set.len(), xs.len(), "{:?}", xs);
+        assert!(xs.len() <= b, "left={}, right={}", xs.len(), b);
+    }
+
+    #[test]
+    fn small_domain() {
+        let mut g = quickcheck::Gen::new(32);
+        let xs = super::gen_unique_vec::<bool, _>(&mut g, 10..);
+        assert!(xs.len() <= 2);
+        assert!(!xs.is_empty());
+    }
+}
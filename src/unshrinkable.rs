@@ -1,10 +1,27 @@
 use quickcheck::{Gen, Arbitrary};
 use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
 
 /// Unshrinkable suppresses element-shrinking of quickcheck.
 ///
 /// Please refer to 4-clause [shrink_a_field] for an example.
-#[derive(Debug, Clone)]
+///
+/// It is for the wrap/unwrap pattern, i.e., values are wrapped by [new](Self::new)
+/// before shrinking and unwrapped by [take](Self::take) after.
+/// For vectors, [shrink_len_only] packages this pattern.
+/// Its `arbitrary` generates nothing to take.
+/// To generate values directly, e.g., as fields, use [ArbitraryUnshrinkable] instead.
+///
+/// Comparisons and hashing delegate to the wrapped value, when `T` supports them.
+/// Values generated by `arbitrary` hold nothing.
+/// They are equal to each other, and less than any wrapped value.
+///
+/// The wrapped value can be inspected without moving by dereferencing or `as_ref`,
+/// which panics if nothing is wrapped.
+///
+/// With the `serde` feature, it (de)serializes transparently as the wrapped value.
+/// Serializing one with nothing wrapped is an error.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Unshrinkable<T: Debug + Clone + 'static>(Option<T>);
 
 impl<T: Debug + Clone> Unshrinkable<T> {
@@ -12,8 +29,44 @@ impl<T: Debug + Clone> Unshrinkable<T> {
         Unshrinkable(Some(x))
     }
 
+    /// Unwraps the value.
+    ///
+    /// It panics if nothing is wrapped, i.e., the value is generated by `arbitrary`.
+    /// Use [into_inner](Self::into_inner) or [get](Self::get) where that may happen.
     pub fn take(self) -> T {
-        self.0.unwrap()
+        self.0.expect(NOTHING_WRAPPED)
+    }
+
+    /// Unwraps the value, or `None` if nothing is wrapped.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+
+    /// Borrows the value, or `None` if nothing is wrapped.
+    pub fn get(&self) -> Option<&T> {
+        self.0.as_ref()
+    }
+}
+
+const NOTHING_WRAPPED: &str = "Unshrinkable: nothing is wrapped, as it is generated by `arbitrary`";
+
+impl<T: Debug + Clone> Deref for Unshrinkable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0.as_ref().expect(NOTHING_WRAPPED)
+    }
+}
+
+impl<T: Debug + Clone> DerefMut for Unshrinkable<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.0.as_mut().expect(NOTHING_WRAPPED)
+    }
+}
+
+impl<T: Debug + Clone> AsRef<T> for Unshrinkable<T> {
+    fn as_ref(&self) -> &T {
+        self
     }
 }
 
@@ -23,6 +76,76 @@ impl<T: Debug + Clone> Arbitrary for Unshrinkable<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: Debug + Clone + serde::Serialize> serde::Serialize for Unshrinkable<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Some(x) => x.serialize(serializer),
+            None => Err(serde::ser::Error::custom(NOTHING_WRAPPED)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Debug + Clone + serde::Deserialize<'de>> serde::Deserialize<'de> for Unshrinkable<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Unshrinkable::new)
+    }
+}
+
+/// Shrinks only the length of a sequence, keeping its elements untouched.
+///
+/// It packages the wrap/unwrap pattern of [Unshrinkable]:
+/// elements are wrapped, the vector is shrinked, and then elements are taken back.
+/// So every candidate consists of original elements, in their original order.
+///
+/// ```rust
+/// use rs_quickcheck_util::shrink_len_only;
+///
+/// for xs in shrink_len_only(&[10, 20, 30]) {
+///     assert!(xs.len() < 3);
+///     assert!(xs.iter().all(|x| [10, 20, 30].contains(x)));
+/// }
+/// ```
+pub fn shrink_len_only<T: Debug + Clone + 'static>(xs: &[T]) -> impl Iterator<Item = Vec<T>> {
+    let xs: Vec<_> = xs.iter().cloned().map(Unshrinkable::new).collect();
+    xs.shrink().map(|xs| xs.into_iter().map(|x| x.take()).collect())
+}
+
+/// Arbitrary values which are never shrinked.
+///
+/// Unlike [Unshrinkable], its `arbitrary` generates a real value by `T::arbitrary`,
+/// so it can be generated directly, e.g., as a field or an element.
+/// Its `shrink` yields nothing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArbitraryUnshrinkable<T>(pub T);
+
+impl<T> ArbitraryUnshrinkable<T> {
+    pub fn take(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ArbitraryUnshrinkable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ArbitraryUnshrinkable<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for ArbitraryUnshrinkable<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        ArbitraryUnshrinkable(T::arbitrary(g))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use quickcheck::Arbitrary;
@@ -37,4 +160,101 @@ mod tests {
             assert!(ys.iter().all(|y| *y == x));
         }
     }
+
+    #[quickcheck]
+    fn sort_and_dedup(xs: Vec<u32>) {
+        let mut ys: Vec<_> = xs.iter().map(|x| super::Unshrinkable::new(*x)).collect();
+        ys.sort();
+        ys.dedup();
+        let mut xs = xs;
+        xs.sort_unstable();
+        xs.dedup();
+        assert_eq!(ys.into_iter().map(|y| y.take()).collect::<Vec<_>>(), xs);
+    }
+
+    #[test]
+    fn empty_ones() {
+        let mut g = quickcheck::Gen::new(32);
+        let x = super::Unshrinkable::<u32>::arbitrary(&mut g);
+        let y = super::Unshrinkable::<u32>::arbitrary(&mut g);
+        assert_eq!(x, y);
+        assert!(x < super::Unshrinkable::new(0));
+        let set: std::collections::HashSet<_> = [x, y].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn deref() {
+        let mut x = super::Unshrinkable::new(vec![1, 2]);
+        assert_eq!(x.len(), 2);
+        x.push(3);
+        assert_eq!(x.as_ref(), &vec![1, 2, 3]);
+        assert_eq!(x.take(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing is wrapped")]
+    fn deref_nothing() {
+        let mut g = quickcheck::Gen::new(32);
+        let x = super::Unshrinkable::<u32>::arbitrary(&mut g);
+        let _ = *x;
+    }
+
+    #[test]
+    fn safe_accessors() {
+        let mut g = quickcheck::Gen::new(32);
+        let x = super::Unshrinkable::<u32>::arbitrary(&mut g);
+        assert_eq!(x.get(), None);
+        assert_eq!(x.into_inner(), None);
+        let x = super::Unshrinkable::new(1u32);
+        assert_eq!(x.get(), Some(&1));
+        assert_eq!(x.into_inner(), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing is wrapped")]
+    fn take_nothing() {
+        let mut g = quickcheck::Gen::new(32);
+        super::Unshrinkable::<u32>::arbitrary(&mut g).take();
+    }
+
+    #[quickcheck]
+    fn shrink_len_only(xs: Vec<u32>) {
+        for ys in super::shrink_len_only(&xs) {
+            assert!(ys.len() < xs.len());
+            let mut it = xs.iter();
+            assert!(ys.iter().all(|y| it.any(|x| x == y)), "{:?} of {:?}", ys, xs);
+        }
+    }
+
+    #[quickcheck]
+    fn arbitrary_unshrinkable(xs: Vec<super::ArbitraryUnshrinkable<u8>>) {
+        for x in xs.iter() {
+            assert_eq!(x.shrink().count(), 0);
+        }
+        let ys: Vec<u8> = xs.iter().cloned().map(|x| x.take()).collect();
+        assert_eq!(ys.len(), xs.len());
+        for xs in xs.shrink() {
+            let _: Vec<u8> = xs.into_iter().map(|x| x.take()).collect();
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let x = super::Unshrinkable::new("hello".to_string());
+        let json = serde_json::to_string(&x).unwrap();
+        assert_eq!(json, "\"hello\"");
+        let y: super::Unshrinkable<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(y, x);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_nothing_wrapped() {
+        let mut g = quickcheck::Gen::new(32);
+        let x = super::Unshrinkable::<String>::arbitrary(&mut g);
+        let err = serde_json::to_string(&x).unwrap_err();
+        assert!(err.to_string().contains("nothing is wrapped"), "{}", err);
+    }
 }
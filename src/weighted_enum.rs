@@ -0,0 +1,200 @@
+use crate::random::gen_below;
+use crate::Entropy;
+
+/// Implements `Arbitrary` for an enum with explicit per-variant weights.
+///
+/// `weighted_enum_arbitrary!(E { w0 => V0, w1 => V1(T0, T1), ... })` implements
+/// `quickcheck::Arbitrary` for enum `E`.
+///
+/// *   `arbitrary` chooses a variant with possibility proportional to its weight,
+///     and generates its fields by their own `arbitrary`.
+///     Zero-weight variants are never chosen.
+/// *   `shrink` collapses towards the earliest (simplest) variant.
+///     It first yields unit variants declared before the current one,
+///     earliest first, and then shrinks fields of the current variant.
+///
+/// Weights are `u32` and they cannot all be zero.
+/// Only unit variants and tuple variants with up to 8 fields are supported.
+///
+/// ```rust
+/// use rs_quickcheck_util::weighted_enum_arbitrary;
+///
+/// #[derive(Debug, Clone)]
+/// enum E {
+///     A,
+///     B(u8),
+///     C(String, bool),
+/// }
+/// weighted_enum_arbitrary!(E {
+///     3 => A,
+///     1 => B(u8),
+///     2 => C(String, bool),
+/// });
+///
+/// let mut g = quickcheck::Gen::new(32);
+/// let _ = <E as quickcheck::Arbitrary>::arbitrary(&mut g);
+/// // a later variant shrinks to the earliest unit variant first
+/// let e = E::C("xyz".to_string(), true);
+/// let first = quickcheck::Arbitrary::shrink(&e).next();
+/// assert!(matches!(first, Some(E::A)), "{:?}", first);
+/// // the earliest variant has no earlier variant to shrink to
+/// assert!(quickcheck::Arbitrary::shrink(&E::A).next().is_none());
+/// ```
+#[macro_export]
+macro_rules! weighted_enum_arbitrary {
+    (
+        $enum:ident {
+            $( $weight:expr => $variant:ident $( ( $($ty:ty),* ) )? ),* $(,)?
+        }
+    ) => {
+        impl quickcheck::Arbitrary for $enum {
+            fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+                let weights: &[u32] = &[$($weight),*];
+                let mut idx = $crate::__weighted_index(g, weights);
+                $(
+                    if idx == 0 {
+                        return $enum::$variant $( (
+                            $( <$ty as quickcheck::Arbitrary>::arbitrary(g) ),*
+                        ) )?;
+                    }
+                    idx -= 1;
+                )*
+                unreachable!()
+            }
+
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                let me = self.clone();
+                let mut idx = 0usize;
+                let mut units: Vec<(usize, $enum)> = vec![];
+                let mut cur = 0usize;
+                $(
+                    if let Some(x) = $crate::weighted_enum_arbitrary!(
+                        @unit $enum, $variant $( ( $($ty),* ) )?
+                    ) {
+                        units.push((cur, x));
+                    }
+                    if let $enum::$variant { .. } = me {
+                        idx = cur;
+                    }
+                    cur += 1;
+                )*
+                let _ = cur;
+                let earlier: Vec<$enum> = units.into_iter()
+                    .filter(|(i, _)| *i < idx)
+                    .map(|(_, x)| x)
+                    .collect();
+                let mut res: Box<dyn Iterator<Item = Self>> = Box::new(earlier.into_iter());
+                $(
+                    $crate::weighted_enum_arbitrary!(
+                        @shrink me, res, $enum, $variant $( ( $($ty),* ) )?
+                    );
+                )*
+                res
+            }
+        }
+    };
+    (@unit $enum:ident, $variant:ident) => {
+        Some($enum::$variant)
+    };
+    (@unit $enum:ident, $variant:ident ( $($ty:ty),* )) => {
+        None
+    };
+    (@shrink $me:ident, $res:ident, $enum:ident, $variant:ident) => {};
+    (@shrink $me:ident, $res:ident, $enum:ident, $variant:ident ( $($ty:ty),* )) => {
+        $crate::weighted_enum_arbitrary!(
+            @bind $me, $res, $enum, $variant, [] [$($ty),*] [f0 f1 f2 f3 f4 f5 f6 f7]
+        );
+    };
+    (
+        @bind $me:ident, $res:ident, $enum:ident, $variant:ident,
+        [$($name:ident : $t:ty),*] [] [$($pool:ident)*]
+    ) => {
+        if let $enum::$variant($($name),*) = &$me {
+            let fields = ($($name.clone(),)*);
+            $res = Box::new($res.chain(
+                quickcheck::Arbitrary::shrink(&fields)
+                    .map(|($($name,)*)| $enum::$variant($($name),*))
+            ));
+        }
+    };
+    (
+        @bind $me:ident, $res:ident, $enum:ident, $variant:ident,
+        [$($name:ident : $t:ty),*] [$t0:ty $(, $ts:ty)*] [$p0:ident $($pool:ident)*]
+    ) => {
+        $crate::weighted_enum_arbitrary!(
+            @bind $me, $res, $enum, $variant,
+            [$($name : $t,)* $p0 : $t0] [$($ts),*] [$($pool)*]
+        );
+    };
+}
+
+/// Chooses an index with possibility proportional to its weight.
+#[doc(hidden)]
+pub fn __weighted_index<E: Entropy + ?Sized>(g: &mut E, weights: &[u32]) -> usize {
+    let total: u64 = weights.iter().map(|x| u64::from(*x)).sum();
+    assert!(total > 0, "weights cannot all be zero");
+    let mut r = gen_below(g, total);
+    for (i, w) in weights.iter().enumerate() {
+        let w = u64::from(*w);
+        if r < w {
+            return i;
+        }
+        r -= w;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum E {
+        A,
+        B(u8),
+        C,
+        D(u16, bool),
+    }
+
+    weighted_enum_arbitrary!(E {
+        3 => A,
+        1 => B(u8),
+        2 => C,
+        0 => D(u16, bool),
+    });
+
+    #[test]
+    fn distribution() {
+        const N: usize = 6000;
+        let mut g = quickcheck::Gen::from_size_and_seed(32, 0x5eed);
+        let mut counts = [0usize; 4];
+        for _ in 0..N {
+            match E::arbitrary(&mut g) {
+                E::A => counts[0] += 1,
+                E::B(_) => counts[1] += 1,
+                E::C => counts[2] += 1,
+                E::D(..) => counts[3] += 1,
+            }
+        }
+        let expected = [3 * N / 6, N / 6, 2 * N / 6, 0];
+        for (c, e) in counts.iter().zip(expected.iter()) {
+            let diff = if c > e {c - e} else {e - c};
+            assert!(diff <= N / 30, "counts={:?}, expected={:?}", counts, expected);
+        }
+    }
+
+    #[test]
+    fn shrink_towards_earliest() {
+        assert_eq!(E::A.shrink().count(), 0);
+        let xs: Vec<_> = E::C.shrink().collect();
+        assert_eq!(xs, vec![E::A]);
+        let xs: Vec<_> = E::B(100).shrink().collect();
+        assert_eq!(xs[0], E::A);
+        assert!(xs[1..].iter().all(|x| matches!(x, E::B(y) if *y < 100)));
+        let xs: Vec<_> = E::D(100, true).shrink().collect();
+        assert_eq!(xs[..2], [E::A, E::C]);
+        assert!(xs[2..].iter().all(|x| matches!(x, E::D(..))));
+        assert!(xs.contains(&E::D(0, true)));
+        assert!(xs.contains(&E::D(100, false)));
+    }
+}
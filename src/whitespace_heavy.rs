@@ -0,0 +1,132 @@
+use crate::gen_bytes::gen_len_by;
+use crate::random::gen_below;
+use quickcheck::{Arbitrary, Gen};
+
+/// A string with diverse whitespace in random runs.
+///
+/// Testing tokenizers against whitespace handling needs strings with diverse
+/// whitespace, e.g., spaces, tabs, newlines, CR, NBSP and various Unicode spaces.
+///
+/// *   `arbitrary` intersperses words of ASCII letters with runs of whitespace
+///     drawn from [WHITESPACES](Self::WHITESPACES).
+///     The number of words, the length of each word and the length of each run
+///     are all exponentially distributed.
+/// *   `shrink` reduces towards a single normal space.
+///     It yields `" "`, the string with every whitespace run collapsed into a single
+///     normal space, and the string with one word or run removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhitespaceHeavy(pub String);
+
+impl WhitespaceHeavy {
+    /// The curated whitespace set.
+    pub const WHITESPACES: &'static [char] = &[
+        ' ', '\t', '\n', '\r', '\u{0B}', '\u{0C}', '\u{85}', '\u{A0}', '\u{1680}',
+        '\u{2000}', '\u{2001}', '\u{2002}', '\u{2003}', '\u{2004}', '\u{2005}',
+        '\u{2006}', '\u{2007}', '\u{2008}', '\u{2009}', '\u{200A}',
+        '\u{2028}', '\u{2029}', '\u{202F}', '\u{205F}', '\u{3000}',
+    ];
+
+    /// Splits into alternating runs of whitespace and non-whitespace.
+    fn runs(&self) -> Vec<&str> {
+        let mut res = vec![];
+        let mut start = 0;
+        let mut last: Option<bool> = None;
+        for (i, c) in self.0.char_indices() {
+            let ws = c.is_whitespace();
+            if last.map_or(false, |x| x != ws) {
+                res.push(&self.0[start..i]);
+                start = i;
+            }
+            last = Some(ws);
+        }
+        if start < self.0.len() {
+            res.push(&self.0[start..]);
+        }
+        res
+    }
+}
+
+impl Arbitrary for WhitespaceHeavy {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut res = String::new();
+        let words = gen_len_by(g, .., |g| gen_below(g, 4) == 0);
+        for i in 0..=words {
+            let run = gen_len_by(g, 1.., |g| gen_below(g, 3) == 0);
+            for _ in 0..run {
+                res.push(*g.choose(Self::WHITESPACES).unwrap());
+            }
+            if i < words {
+                let word = crate::gen_bytes(g, b"abcdefghijklmnopqrstuvwxyz.", b'.', 1..);
+                for ch in word {
+                    if gen_below(g, 2) == 0 {
+                        res.push(ch.to_ascii_uppercase() as char);
+                    } else {
+                        res.push(ch as char);
+                    }
+                }
+            }
+        }
+        WhitespaceHeavy(res)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let mut res = vec![];
+        if !self.0.is_empty() && self.0 != " " {
+            res.push(WhitespaceHeavy(" ".to_string()));
+        }
+        let runs = self.runs();
+        let collapsed: String = runs.iter()
+            .map(|r| if r.starts_with(char::is_whitespace) {" "} else {r})
+            .collect();
+        if collapsed != self.0 {
+            res.push(WhitespaceHeavy(collapsed));
+        }
+        for i in 0..runs.len() {
+            let removed: String = runs.iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, r)| *r)
+                .collect();
+            res.push(WhitespaceHeavy(removed));
+        }
+        Box::new(res.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WhitespaceHeavy;
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn diverse_whitespace() {
+        let mut g = quickcheck::Gen::new(32);
+        let spaces: BTreeSet<char> = (0..100)
+            .flat_map(|_| WhitespaceHeavy::arbitrary(&mut g).0.chars().collect::<Vec<_>>())
+            .filter(|c| c.is_whitespace())
+            .collect();
+        assert!(spaces.len() > 10, "{:?}", spaces);
+        assert!(WhitespaceHeavy::WHITESPACES.iter().all(|c| c.is_whitespace()));
+    }
+
+    #[quickcheck]
+    fn shrink(trial: WhitespaceHeavy) {
+        let n = trial.0.chars().count();
+        for x in trial.shrink() {
+            assert!(x.0.chars().count() <= n, "{:?} -> {:?}", trial, x);
+            assert_ne!(x, trial);
+        }
+    }
+
+    #[test]
+    fn towards_single_space() {
+        let x = WhitespaceHeavy("\t\u{3000}ab\r\ncd ".to_string());
+        let xs: Vec<_> = x.shrink().collect();
+        assert_eq!(xs[0].0, " ");
+        assert_eq!(xs[1].0, " ab cd ");
+        assert!(xs.contains(&WhitespaceHeavy("ab\r\ncd ".to_string())));
+        assert_eq!(WhitespaceHeavy(" ".to_string()).shrink().next(), Some(WhitespaceHeavy(String::new())));
+    }
+}